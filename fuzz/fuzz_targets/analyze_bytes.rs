@@ -0,0 +1,34 @@
+//! Feeds arbitrary bytes to `analyze_sales_sprints_bytes`.
+//!
+//! Run from the crate root with a nightly toolchain:
+//!
+//! ```sh
+//! cargo install cargo-fuzz
+//! cargo +nightly fuzz run analyze_bytes
+//! ```
+//!
+//! Besides not panicking, every outcome must have its winner scoring exactly
+//! `s * t` points, and short inputs must agree with the reference
+//! implementation.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sales_sprint_log_analyzer::{analyze_sales_sprints_bytes, reference};
+
+/// Inputs past this length skip the quadratic reference comparison.
+const REFERENCE_LIMIT: usize = 4096;
+
+fuzz_target!(|data: &[u8]| {
+    let outcomes = analyze_sales_sprints_bytes(data);
+    let a = data.iter().filter(|&&b| b == b'A').count();
+    let b = data.iter().filter(|&&b| b == b'B').count();
+    for o in &outcomes {
+        let winner_points = if o.winner == 'A' { a } else { b };
+        assert_eq!(o.s * o.t, winner_points, "{:?} should account for all of the winner's points", o);
+        assert!(o.s * o.t <= a + b, "{:?} should not exceed the competitor count", o);
+    }
+    if data.len() <= REFERENCE_LIMIT {
+        let log = String::from_utf8_lossy(data);
+        assert_eq!(outcomes, reference::analyze_sales_sprints(&log));
+    }
+});
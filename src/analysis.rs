@@ -0,0 +1,1061 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use crate::config::AnalysisConfig;
+use crate::divisors::{divisor_count, divisor_pairs, is_prime};
+use crate::error::SprintError;
+use crate::outcome::{common_structures, AnalysisResult, GenericOutcome, Outcome, Outcomes};
+use crate::stream::{Side, Stream};
+
+pub(crate) fn is_competitor(b: u8) -> bool {
+    b == b'A' || b == b'B'
+}
+
+pub(crate) fn classify(b: u8) -> Option<Side> {
+    match b {
+        b'A' => Some(Side::First),
+        b'B' => Some(Side::Second),
+        _ => None,
+    }
+}
+
+pub(crate) fn stream_of(log: &str) -> Stream {
+    stream_of_bytes(log.as_bytes())
+}
+
+pub(crate) fn stream_of_bytes(bytes: &[u8]) -> Stream {
+    Stream::from_sides(bytes.iter().copied().filter_map(classify))
+}
+
+/// The log with every character other than `'A'` and `'B'` removed.
+///
+/// Multi-byte UTF-8 sequences never contain ASCII bytes, so filtering bytes is
+/// the same as filtering chars.
+pub fn clean_log(log: &str) -> Vec<u8> {
+    #[cfg(feature = "simd")]
+    {
+        crate::simd::clean(log.as_bytes())
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        log.bytes().filter(|&b| is_competitor(b)).collect()
+    }
+}
+
+/// [`clean_log`] along with the byte offset in `log` of each cleaned byte,
+/// so positions in the cleaned stream (such as sprint ends) can be mapped
+/// back onto the raw text.
+pub fn clean_with_map(log: &str) -> (Vec<u8>, Vec<usize>) {
+    log.bytes()
+        .enumerate()
+        .filter(|&(_, b)| is_competitor(b))
+        .map(|(i, b)| (b, i))
+        .unzip()
+}
+
+/// Number of `'A'` and `'B'` characters in the log.
+pub fn competitor_count(log: &str) -> usize {
+    #[cfg(feature = "simd")]
+    {
+        crate::simd::competitor_count(log.as_bytes())
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        log.bytes().filter(|&b| is_competitor(b)).count()
+    }
+}
+
+/// The competitor with the longest unbroken run in the cleaned log and that
+/// run's length, the earliest on a tie; `('\0', 0)` if there is none.
+pub fn longest_run(log: &str) -> (char, usize) {
+    let mut longest = ('\0', 0);
+    let mut current = ('\0', 0);
+    for b in log.bytes().filter(|&b| is_competitor(b)) {
+        let c = b as char;
+        current = if current.0 == c {
+            (c, current.1 + 1)
+        } else {
+            (c, 1)
+        };
+        if current.1 > longest.1 {
+            longest = current;
+        }
+    }
+    longest
+}
+
+/// Number of `(s, t, winner)` candidates the analysis has to check.
+///
+/// Every sprint of a valid outcome is won by the same side, so the winner
+/// scores exactly `s * t` points and `t` must divide the winner's total. The
+/// candidates are therefore the divisors of each side's point count, which is
+/// `divisor_count(competitor_count(log))` for a single-competitor log and
+/// an upper bound on the outcome count in general.
+pub fn candidate_count(log: &str) -> usize {
+    let a = log.bytes().filter(|&b| b == b'A').count();
+    let b = log.bytes().filter(|&b| b == b'B').count();
+    divisor_count(a) + divisor_count(b)
+}
+
+/// Cleaned index of the point that first brings `'A'` and `'B'` level, or
+/// `None` if the score is never tied after the start.
+pub fn balance_point(log: &str) -> Option<usize> {
+    let stream = stream_of(log);
+    (2..=stream.len())
+        .step_by(2)
+        .find(|&len| 2 * stream.points_in(Side::First, 0, len) == len)
+        .map(|len| len - 1)
+}
+
+/// Whether `'A'` and `'B'` scored the same number of points, which an empty
+/// log trivially does.
+///
+/// Neither side can then win a single sprint covering the whole log, since
+/// that needs the loser to score fewer points than the winner.
+pub fn is_balanced(log: &str) -> bool {
+    let a = log.bytes().filter(|&b| b == b'A').count();
+    2 * a == competitor_count(log)
+}
+
+/// Whether the log has a prime number of competitor characters.
+///
+/// A single-competitor log of prime length can only be read as `(1, n)` or
+/// `(n, 1)`, so callers can special-case it without running the analysis.
+pub fn is_prime_length(log: &str) -> bool {
+    is_prime(competitor_count(log))
+}
+
+/// Calls `visit` with the `(s, t, winner)` of every valid outcome of the
+/// stream, in candidate order rather than sorted order. Candidates rejected
+/// by `keep` are skipped without being validated.
+pub(crate) fn visit_sweep_sides_where<K, F>(stream: &Stream, keep: K, mut visit: F)
+where
+    K: Fn(usize, usize) -> bool,
+    F: FnMut(usize, usize, Side),
+{
+    for side in [Side::First, Side::Second] {
+        for (s, t) in divisor_pairs(stream.points(side)) {
+            if keep(s, t) && stream.is_sweep(s, t, side) {
+                #[cfg(debug_assertions)]
+                {
+                    let (consumed, end) = sweep_consumption(stream, t, side);
+                    debug_assert_eq!(
+                        end,
+                        stream.len(),
+                        "({}, {}) should end on the last point",
+                        s,
+                        t
+                    );
+                    debug_assert_eq!(
+                        consumed,
+                        s * t,
+                        "({}, {}) should use all of the winner's points",
+                        s,
+                        t
+                    );
+                }
+                visit(s, t, side);
+            }
+        }
+    }
+}
+
+/// Replays the sprints of target `t`, returning how many of `side`'s points
+/// they consumed and where the last one ended.
+#[cfg(debug_assertions)]
+fn sweep_consumption(stream: &Stream, t: usize, side: Side) -> (usize, usize) {
+    let (mut consumed, mut end) = (0, 0);
+    for sprint in stream.sprints(t) {
+        consumed += stream.points_in(side, end, sprint.end);
+        end = sprint.end;
+    }
+    (consumed, end)
+}
+
+/// [`visit_sweep_sides_where`] over every candidate.
+pub(crate) fn visit_sweep_sides<F: FnMut(usize, usize, Side)>(stream: &Stream, visit: F) {
+    visit_sweep_sides_where(stream, |_, _| true, visit);
+}
+
+/// The sorted outcomes of the log among the `(s, t)` candidates accepted by
+/// `keep`.
+pub(crate) fn outcomes_where<K: Fn(usize, usize) -> bool>(log: &str, keep: K) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+    visit_sweep_sides_where(&stream_of(log), keep, |s, t, side| {
+        outcomes.push(Outcome::new(s, t, winner_char(side, 'A', 'B')))
+    });
+    outcomes.sort();
+    outcomes
+}
+
+pub(crate) fn winner_char(side: Side, first: char, second: char) -> char {
+    match side {
+        Side::First => first,
+        Side::Second => second,
+    }
+}
+
+/// [`visit_sweep_sides`] with the sides named `first` and `second`.
+pub(crate) fn visit_sweeps<F: FnMut(Outcome)>(
+    stream: &Stream,
+    first: char,
+    second: char,
+    mut visit: F,
+) {
+    visit_sweep_sides(stream, |s, t, side| {
+        visit(Outcome::new(s, t, winner_char(side, first, second)))
+    });
+}
+
+fn reverse_sweep_outcomes(stream: &Stream, first: char, second: char) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+    for (side, winner) in [(Side::First, first), (Side::Second, second)] {
+        for (s, t) in divisor_pairs(stream.points(side)) {
+            if stream.is_sweep_backward(s, t, side) {
+                outcomes.push(Outcome::new(s, t, winner));
+            }
+        }
+    }
+    outcomes.sort();
+    outcomes
+}
+
+pub(crate) fn sweep_outcomes(stream: &Stream, first: char, second: char) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+    visit_sweeps(stream, first, second, |o| outcomes.push(o));
+    outcomes.sort();
+    outcomes
+}
+
+/// Every valid `(s, t, winner)` reading of the log, sorted by `s` then `t`.
+pub fn analyze_sales_sprints(log: &str) -> Vec<Outcome> {
+    analyze_sales_sprints_bytes(log.as_bytes())
+}
+
+/// [`analyze_sales_sprints`] over raw bytes, which need not be UTF-8: every
+/// byte other than `b'A'` and `b'B'` is ignored.
+pub fn analyze_sales_sprints_bytes(bytes: &[u8]) -> Vec<Outcome> {
+    let stream = stream_of_bytes(bytes);
+    let outcomes = sweep_outcomes(&stream, 'A', 'B');
+    debug_assert_eq!(outcomes, reverse_sweep_outcomes(&stream, 'A', 'B'));
+    outcomes
+}
+
+/// [`analyze_sales_sprints`] collected into a vector preallocated for `cap`
+/// outcomes, such as a [`candidate_count`] estimate, so a large result set
+/// is never reallocated.
+pub fn analyze_sales_sprints_with_capacity(log: &str, cap: usize) -> Vec<Outcome> {
+    let mut outcomes = Vec::with_capacity(cap);
+    visit_sweeps(&stream_of(log), 'A', 'B', |o| outcomes.push(o));
+    outcomes.sort();
+    outcomes
+}
+
+/// Analyzes any `char` stream, with `first` and `second` as the competitors
+/// and every other char ignored. Outcomes are won by `first` or `second`.
+///
+/// Only the point index the analysis needs is built; the chars themselves
+/// are never collected.
+pub fn analyze_chars<I: IntoIterator<Item = char>>(
+    chars: I,
+    first: char,
+    second: char,
+) -> Vec<Outcome> {
+    let stream = Stream::from_sides(chars.into_iter().filter_map(|c| {
+        if c == first {
+            Some(Side::First)
+        } else if c == second {
+            Some(Side::Second)
+        } else {
+            None
+        }
+    }));
+    sweep_outcomes(&stream, first, second)
+}
+
+/// Analyzes UTF-16 code units, with `first` and `second` as the competitors.
+///
+/// Only competitors in the Basic Multilingual Plane are supported, since each
+/// must be a single code unit; a competitor outside it never matches. Every
+/// other unit, including both halves of a surrogate pair, is ignored.
+pub fn analyze_utf16(units: &[u16], first: char, second: char) -> Vec<Outcome> {
+    let unit_of = |c: char| u16::try_from(u32::from(c)).ok();
+    let (first_unit, second_unit) = (unit_of(first), unit_of(second));
+    let stream = Stream::from_sides(units.iter().filter_map(|&u| {
+        if Some(u) == first_unit {
+            Some(Side::First)
+        } else if Some(u) == second_unit {
+            Some(Side::Second)
+        } else {
+            None
+        }
+    }));
+    sweep_outcomes(&stream, first, second)
+}
+
+/// Analyzes a packed event stream, one bit per point: 0 for `'A'` and 1 for
+/// `'B'`, with nothing to ignore. Behind the `bitvec` feature.
+#[cfg(feature = "bitvec")]
+pub fn analyze_bits(bits: &bitvec::slice::BitSlice) -> Vec<Outcome> {
+    let stream =
+        Stream::from_sides(bits.iter().by_vals().map(
+            |bit| {
+                if bit {
+                    Side::Second
+                } else {
+                    Side::First
+                }
+            },
+        ));
+    sweep_outcomes(&stream, 'A', 'B')
+}
+
+/// [`analyze_chars`] over the events whose confidence is at least
+/// `threshold`; less certain events are dropped before any sprint is played.
+pub fn analyze_weighted_input(
+    chars: &[(char, f64)],
+    threshold: f64,
+    first: char,
+    second: char,
+) -> Vec<Outcome> {
+    analyze_chars(
+        chars
+            .iter()
+            .filter(|&&(_, confidence)| confidence >= threshold)
+            .map(|&(c, _)| c),
+        first,
+        second,
+    )
+}
+
+/// How [`analyze_with_table`] treats a byte value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CharClass {
+    First,
+    Second,
+    #[default]
+    Ignore,
+}
+
+/// Analyzes bytes classified by a 256-entry lookup table. The table replaces
+/// per-byte comparisons against the competitor characters, but the loop still
+/// branches on each looked-up class to skip or record the byte.
+///
+/// Outcomes name the [`CharClass::First`] side `'A'` and the
+/// [`CharClass::Second`] side `'B'`, as the default analysis does.
+pub fn analyze_with_table(bytes: &[u8], table: &[CharClass; 256]) -> Vec<Outcome> {
+    let stream = Stream::from_sides(bytes.iter().filter_map(|&b| match table[b as usize] {
+        CharClass::First => Some(Side::First),
+        CharClass::Second => Some(Side::Second),
+        CharClass::Ignore => None,
+    }));
+    sweep_outcomes(&stream, 'A', 'B')
+}
+
+/// [`analyze_sales_sprints`] that rejects any character other than `'A'` and
+/// `'B'` with [`SprintError::InvalidCharacter`].
+pub fn analyze_sales_sprints_strict(log: &str) -> Result<Vec<Outcome>, SprintError> {
+    AnalysisConfig::default().analyze_strict(log)
+}
+
+/// The byte offset of the first character [`analyze_sales_sprints_strict`]
+/// would reject, with up to `radius` characters either side of it and the
+/// character itself in brackets, as in `"AB[!]AB"`.
+pub fn first_invalid_context(log: &str, radius: usize) -> Option<(usize, String)> {
+    let (pos, (index, ch)) = log
+        .char_indices()
+        .enumerate()
+        .find(|&(_, (_, c))| c != 'A' && c != 'B')?;
+    let before: String = log
+        .chars()
+        .skip(pos.saturating_sub(radius))
+        .take(pos.min(radius))
+        .collect();
+    let after: String = log[index + ch.len_utf8()..].chars().take(radius).collect();
+    Some((index, format!("{}[{}]{}", before, ch, after)))
+}
+
+/// The outcomes a log of `n` copies of `winner` would have, without building
+/// the log: one per divisor pair of `n`.
+pub fn all_outcomes_for_length(n: usize, winner: char) -> Vec<Outcome> {
+    divisor_pairs(n)
+        .into_iter()
+        .map(|(s, t)| Outcome::new(s, t, winner))
+        .collect()
+}
+
+/// The all-identical log whose outcome set is exactly `outcomes`, in any
+/// order: `winner` repeated `n` times when the outcomes are every divisor pair
+/// of `n`, all won by `winner`. `None` for any other set, including an empty
+/// one, or one whose `s * t` overflows or names a log too long to allocate.
+///
+/// Every outcome is checked against the first one's `n` and winner before
+/// the divisors of `n` are enumerated, and the log is only built once the
+/// set is known to match.
+pub fn canonical_log(outcomes: &[Outcome]) -> Option<String> {
+    let first = outcomes.first()?;
+    let n = first.s.checked_mul(first.t)?;
+    let bytes = n.checked_mul(first.winner.len_utf8())?;
+    if bytes > isize::MAX as usize
+        || outcomes
+            .iter()
+            .any(|o| o.winner != first.winner || o.s.checked_mul(o.t) != Some(n))
+    {
+        return None;
+    }
+    let mut sorted = outcomes.to_vec();
+    sorted.sort();
+    (sorted == all_outcomes_for_length(n, first.winner)).then(|| first.winner.to_string().repeat(n))
+}
+
+/// The outcomes with at most `max_s` sprints; larger candidates are never
+/// simulated.
+pub fn analyze_max_sprints(log: &str, max_s: usize) -> Vec<Outcome> {
+    outcomes_where(log, |s, _| s <= max_s)
+}
+
+/// [`analyze_sales_sprints`], or nothing when the log has fewer than
+/// `min_chars` competitor characters.
+pub fn analyze_min_length(log: &str, min_chars: usize) -> Vec<Outcome> {
+    if competitor_count(log) < min_chars {
+        return Vec::new();
+    }
+    analyze_sales_sprints(log)
+}
+
+/// The outcomes with `lo <= t <= hi`; targets outside the range are never
+/// simulated.
+pub fn analyze_target_range(log: &str, lo: usize, hi: usize) -> Vec<Outcome> {
+    outcomes_where(log, |_, t| (lo..=hi).contains(&t))
+}
+
+/// The outcomes keyed by target. A target fixes every sprint boundary, so
+/// each valid `t` has exactly one outcome.
+pub fn outcomes_by_target(log: &str) -> HashMap<usize, Outcome> {
+    let mut by_target = HashMap::new();
+    visit_sweeps(&stream_of(log), 'A', 'B', |o| {
+        by_target.insert(o.t, o);
+    });
+    by_target
+}
+
+/// The outcomes found before `budget` runs out, sorted, and whether every
+/// candidate was checked in time.
+///
+/// The clock is read before each candidate, so a single slow candidate can
+/// overrun the budget, but no new one is started after it has passed.
+pub fn analyze_timed(log: &str, budget: Duration) -> (Vec<Outcome>, bool) {
+    let start = Instant::now();
+    let stream = stream_of(log);
+    let mut outcomes = Vec::new();
+    let mut complete = true;
+    'sides: for side in [Side::First, Side::Second] {
+        for (s, t) in divisor_pairs(stream.points(side)) {
+            if start.elapsed() >= budget {
+                complete = false;
+                break 'sides;
+            }
+            if stream.is_sweep(s, t, side) {
+                outcomes.push(Outcome::new(s, t, winner_char(side, 'A', 'B')));
+            }
+        }
+    }
+    outcomes.sort();
+    (outcomes, complete)
+}
+
+/// Sends each outcome down `tx` as soon as it is validated, in candidate
+/// order rather than sorted order. Stops early once the receiver hangs up.
+pub fn analyze_to_channel(log: &str, tx: Sender<Outcome>) {
+    let stream = stream_of(log);
+    for side in [Side::First, Side::Second] {
+        for (s, t) in divisor_pairs(stream.points(side)) {
+            if !stream.is_sweep(s, t, side) {
+                continue;
+            }
+            let outcome = Outcome::new(s, t, winner_char(side, 'A', 'B'));
+            if tx.send(outcome).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// [`analyze_sales_sprints`], calling `on_progress` with the fraction of
+/// candidates validated so far after each one.
+///
+/// The fractions strictly increase and the last call always reports 1.0,
+/// including for a log with no candidates at all.
+pub fn analyze_with_progress(log: &str, mut on_progress: impl FnMut(f32)) -> Vec<Outcome> {
+    let stream = stream_of(log);
+    let total =
+        divisor_count(stream.points(Side::First)) + divisor_count(stream.points(Side::Second));
+    let mut done = 0;
+    let mut outcomes = Vec::new();
+    for side in [Side::First, Side::Second] {
+        for (s, t) in divisor_pairs(stream.points(side)) {
+            if stream.is_sweep(s, t, side) {
+                outcomes.push(Outcome::new(s, t, winner_char(side, 'A', 'B')));
+            }
+            done += 1;
+            on_progress(done as f32 / total as f32);
+        }
+    }
+    if total == 0 {
+        on_progress(1.0);
+    }
+    outcomes.sort();
+    outcomes
+}
+
+/// [`analyze_sales_sprints`] plus the number of characters (not bytes) that
+/// were skipped for being neither `'A'` nor `'B'`, tallied in the same pass.
+pub fn analyze_with_ignored_count(log: &str) -> (Vec<Outcome>, usize) {
+    let mut ignored = 0;
+    let stream = Stream::from_sides(log.chars().filter_map(|c| match c {
+        'A' => Some(Side::First),
+        'B' => Some(Side::Second),
+        _ => {
+            ignored += 1;
+            None
+        }
+    }));
+    (sweep_outcomes(&stream, 'A', 'B'), ignored)
+}
+
+/// The outcomes where the match is first to `win_target` sprint wins,
+/// whatever the loser manages in between, and the log ends on the deciding
+/// sprint. Each outcome's `s` is `win_target`.
+///
+/// The default derivation is the special case where the loser takes no
+/// sprint, so `s` is both the win target and the number of sprints played:
+/// every outcome of [`analyze_sales_sprints`] with `s == win_target` is also
+/// returned here.
+pub fn analyze_fixed_win_target(log: &str, win_target: usize) -> Vec<Outcome> {
+    let stream = stream_of(log);
+    let mut outcomes = Vec::new();
+    if win_target == 0 {
+        return outcomes;
+    }
+    for t in 1..=stream.len() {
+        let (mut first, mut second) = (0, 0);
+        for sprint in stream.sprints(t) {
+            let wins = match sprint.winner {
+                Side::First => &mut first,
+                Side::Second => &mut second,
+            };
+            *wins += 1;
+            if *wins == win_target {
+                if sprint.end == stream.len() {
+                    outcomes.push(Outcome::new(
+                        win_target,
+                        t,
+                        winner_char(sprint.winner, 'A', 'B'),
+                    ));
+                }
+                break;
+            }
+        }
+    }
+    outcomes.sort();
+    outcomes
+}
+
+/// The outcomes where `winner` has taken `s` sprints of target `t` in a row
+/// from the start, whatever comes after them.
+///
+/// This is much looser than [`analyze_sales_sprints`], which requires the
+/// `s`-th sprint to close on the log's last point. Here the match is over as
+/// soon as it is decided, and any later competitor characters, complete
+/// sprints included, are treated as extra. Every default outcome is still
+/// returned, but so is `(k, t, winner)` for each shorter opening run, which
+/// means a log with any competitor character always has at least `(1, 1, _)`.
+pub fn analyze_allow_early(log: &str) -> Vec<Outcome> {
+    let stream = stream_of(log);
+    let mut outcomes = Vec::new();
+    for t in 1..=stream.len() {
+        let mut sprints = stream.sprints(t);
+        let Some(opener) = sprints.next() else {
+            break;
+        };
+        let winner = winner_char(opener.winner, 'A', 'B');
+        outcomes.push(Outcome::new(1, t, winner));
+        for (k, sprint) in (2..).zip(sprints) {
+            if sprint.winner != opener.winner {
+                break;
+            }
+            outcomes.push(Outcome::new(k, t, winner));
+        }
+    }
+    outcomes.sort();
+    outcomes
+}
+
+/// Whether targets `t1` and `t2` end their first `up_to_sprints` complete
+/// sprints at the same cleaned positions. A target that runs out of complete
+/// sprints sooner than the other disagrees with it.
+///
+/// The larger target needs more points from one side before it can close a
+/// sprint, so two different targets part ways at the first sprint that
+/// either of them completes.
+pub fn boundaries_agree(log: &str, t1: usize, t2: usize, up_to_sprints: usize) -> bool {
+    let stream = stream_of(log);
+    let ends = |t: usize| {
+        let sprints = (t > 0).then(|| stream.sprints(t));
+        sprints
+            .into_iter()
+            .flatten()
+            .map(|sprint| sprint.end)
+            .take(up_to_sprints)
+    };
+    ends(t1).eq(ends(t2))
+}
+
+/// The winner of each complete sprint of target `t`, in order, whether or not
+/// the log decomposes cleanly; a trailing partial sprint is left out.
+pub fn running_winners(log: &str, t: usize) -> Vec<char> {
+    if t == 0 {
+        return Vec::new();
+    }
+    stream_of(log)
+        .sprints(t)
+        .map(|sprint| winner_char(sprint.winner, 'A', 'B'))
+        .collect()
+}
+
+/// A hash of who won each sprint at target `t`, in order, or `None` when the
+/// sprints of target `t` don't end exactly on the log's last point.
+///
+/// Two logs that play out the same way at `t` hash equal, whatever noise or
+/// sprint lengths separate them. The hash is only stable within one build.
+pub fn sprint_sequence_hash(log: &str, t: usize) -> Option<u64> {
+    let stream = stream_of(log);
+    if t == 0 || stream.len() == 0 {
+        return None;
+    }
+    let mut hasher = DefaultHasher::new();
+    let mut end = 0;
+    for sprint in stream.sprints(t) {
+        winner_char(sprint.winner, 'A', 'B').hash(&mut hasher);
+        end = sprint.end;
+    }
+    (end == stream.len()).then(|| hasher.finish())
+}
+
+/// The outcomes under a ruleset where any character other than `'A'` and
+/// `'B'` is noise that aborts the sprint in progress.
+///
+/// The default analysis skips noise in place, so `"AA!AA"` is still one
+/// sprint of target 4. Here the `'!'` throws away the two points before it
+/// and a fresh sprint starts after it, so only uninterrupted runs of
+/// competitor characters can form sprints. Aborted points count for nobody;
+/// the reading is valid when the completed sprints are all won by one side
+/// and no sprint is left in progress at the end of the log.
+pub fn analyze_reset_on_noise(log: &str) -> Vec<Outcome> {
+    let mut segments: Vec<Stream> = log
+        .as_bytes()
+        .split(|&b| !is_competitor(b))
+        .map(stream_of_bytes)
+        .collect();
+    // `split` always yields the segment after the last noise byte, possibly
+    // empty; it is the only one that can't end on an aborted sprint.
+    let tail = segments.pop().expect("split yields at least one segment");
+    segments.retain(|segment| segment.len() > 0);
+    segments.sort_by_key(|segment| Reverse(segment.len()));
+    let longest = segments.first().map_or(0, Stream::len).max(tail.len());
+    let mut outcomes = Vec::new();
+    // A segment shorter than `t` can't complete a sprint, so each target only
+    // replays the segments at least that long.
+    for t in 1..=longest {
+        let (mut s, mut winner) = match tail.sweep_at(t) {
+            Some((s, side)) => (s, Some(side)),
+            None if tail.len() == 0 => (0, None),
+            None => continue,
+        };
+        let mut swept = true;
+        'segments: for segment in segments.iter().take_while(|segment| segment.len() >= t) {
+            for sprint in segment.sprints(t) {
+                if *winner.get_or_insert(sprint.winner) != sprint.winner {
+                    swept = false;
+                    break 'segments;
+                }
+                s += 1;
+            }
+        }
+        if let (Some(side), true) = (winner, swept) {
+            outcomes.push(Outcome::new(s, t, winner_char(side, 'A', 'B')));
+        }
+    }
+    outcomes.sort();
+    outcomes
+}
+
+/// The outcomes when every competitor character is worth `step` points and a
+/// sprint ends as soon as a side has at least `t` of them.
+///
+/// The default rule closes a sprint on a count of exactly `t`, which with one
+/// point per character is the same as reaching it. Here a count can jump past
+/// `t`, so a sprint closes on the side's `ceil(t / step)`-th character and
+/// every target in `(k - 1) * step + 1..=k * step` draws the same boundaries
+/// as a one-point target of `k`, so each `k` is simulated once. Targets run
+/// up to `step` times the cleaned length, and a `step` of 0 never ends a
+/// sprint.
+///
+/// Returns `None` when that bound overflows `usize`, since some targets of
+/// the answer could not be represented.
+pub fn analyze_reach_or_exceed(log: &str, step: usize) -> Option<Vec<Outcome>> {
+    let stream = stream_of(log);
+    let mut outcomes = Vec::new();
+    step.checked_mul(stream.len())?;
+    if step == 0 {
+        return Some(outcomes);
+    }
+    for k in 1..=stream.len() {
+        if let Some((s, side)) = stream.sweep_at(k) {
+            let winner = winner_char(side, 'A', 'B');
+            outcomes.extend(((k - 1) * step + 1..=k * step).map(|t| Outcome::new(s, t, winner)));
+        }
+    }
+    outcomes.sort();
+    Some(outcomes)
+}
+
+/// The outcome when a sprint ends as soon as `boundary(a, b)` holds for the
+/// points `a` and `b` scored in it so far, won by whoever scored the closing
+/// point.
+///
+/// `|a, b| a == t || b == t` reproduces the default rule at target `t`. A
+/// predicate fixes the boundaries, so there is at most one reading: every
+/// sprint must go to the same side with nothing left over, and its `t` is the
+/// winner's points per sprint, which must be the same in each sprint for the
+/// reading to count.
+pub fn analyze_with_predicate(log: &str, boundary: impl Fn(usize, usize) -> bool) -> Vec<Outcome> {
+    let (mut a, mut b) = (0, 0);
+    let mut reading: Option<(usize, usize, Side)> = None;
+    for side in log.bytes().filter_map(classify) {
+        match side {
+            Side::First => a += 1,
+            Side::Second => b += 1,
+        }
+        if !boundary(a, b) {
+            continue;
+        }
+        let t = if side == Side::First { a } else { b };
+        reading = match reading {
+            None => Some((1, t, side)),
+            Some((s, t0, winner)) if t0 == t && winner == side => Some((s + 1, t, side)),
+            Some(_) => return Vec::new(),
+        };
+        (a, b) = (0, 0);
+    }
+    match reading {
+        Some((s, t, side)) if a == 0 && b == 0 => {
+            vec![Outcome::new(s, t, winner_char(side, 'A', 'B'))]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Running majority tallies over every window of `t` consecutive cleaned
+/// characters, sliding one character at a time.
+///
+/// Unlike the sprint decomposition, windows overlap and no surplus is ever
+/// cleared. Entry `i` is `(a_won, b_won)` counted over the windows starting
+/// at cleaned indices `0..=i`, where a window is won by the side with more
+/// points in it and a level window (possible for even `t`) counts for
+/// neither. There is one entry per window, so the last entry is the total,
+/// and the result is empty when `t` is 0 or longer than the cleaned log.
+pub fn analyze_sliding(log: &str, t: usize) -> Vec<(usize, usize)> {
+    let stream = stream_of(log);
+    if t == 0 || t > stream.len() {
+        return Vec::new();
+    }
+    let (mut a_won, mut b_won) = (0, 0);
+    (0..=stream.len() - t)
+        .map(|start| {
+            let a = stream.points_in(Side::First, start, start + t);
+            match (2 * a).cmp(&t) {
+                Ordering::Greater => a_won += 1,
+                Ordering::Less => b_won += 1,
+                Ordering::Equal => {}
+            }
+            (a_won, b_won)
+        })
+        .collect()
+}
+
+/// The outcomes when the cleaned log is scored `step` characters at a time
+/// and a sprint ends after the first step in which a side has at least `t`
+/// points. If both sides get there in the same step, the sprint goes to
+/// `tie_winner`.
+///
+/// A sprint always closes on a step boundary, so the whole step belongs to
+/// it and a count may overshoot `t`. A trailing step shorter than `step` is
+/// scored like any other. With `step == 1` only one side can score per step,
+/// ties never happen, and the result is [`analyze_sales_sprints`]; a `step`
+/// of 0 scores nothing.
+pub fn analyze_step_points(log: &str, step: usize, tie_winner: char) -> Vec<Outcome> {
+    let cleaned = clean_log(log);
+    let mut outcomes = Vec::new();
+    if step == 0 {
+        return outcomes;
+    }
+    // Points before each step, and for every running total the step in which
+    // a side first reaches it, so a sprint's closing step is two lookups away
+    // and each target replays one step per sprint rather than per character.
+    let (mut before_a, mut before_b) = (vec![0], vec![0]);
+    let (mut reach_a, mut reach_b) = (vec![0], vec![0]);
+    for (i, chunk) in cleaned.chunks(step).enumerate() {
+        let a_points = chunk.iter().filter(|&&c| c == b'A').count();
+        reach_a.resize(reach_a.len() + a_points, i);
+        reach_b.resize(reach_b.len() + chunk.len() - a_points, i);
+        before_a.push(reach_a.len() - 1);
+        before_b.push(reach_b.len() - 1);
+    }
+    let steps = before_a.len() - 1;
+    for t in 1..=cleaned.len() {
+        let (mut start, mut s) = (0, 0);
+        let mut winner = None;
+        while start < steps {
+            let a_end = reach_a.get(before_a[start] + t);
+            let b_end = reach_b.get(before_b[start] + t);
+            let (end, side) = match (a_end, b_end) {
+                (Some(a), Some(b)) if a == b => (a, tie_winner),
+                (Some(a), Some(b)) if b < a => (b, 'B'),
+                (Some(a), _) => (a, 'A'),
+                (None, Some(b)) => (b, 'B'),
+                (None, None) => break,
+            };
+            if *winner.get_or_insert(side) != side {
+                break;
+            }
+            s += 1;
+            start = end + 1;
+        }
+        if let (Some(winner), true) = (winner, start == steps) {
+            outcomes.push(Outcome::new(s, t, winner));
+        }
+    }
+    outcomes.sort();
+    outcomes
+}
+
+/// Analyzes each `sep`-separated match of a concatenated log on its own, in
+/// order. Segments with no valid reading, including empty ones, give an empty
+/// list.
+pub fn analyze_multi(log: &str, sep: char) -> Vec<Vec<Outcome>> {
+    log.split(sep).map(analyze_sales_sprints).collect()
+}
+
+/// The winner if the whole log is one sprint of target `n` (the `(1, n)`
+/// outcome, with `n` the competitor count), else `None`.
+///
+/// That sprint only closes on the log's last point if the winner scored every
+/// point, so this is a single scan checking that one competitor appears.
+pub fn is_single_sprint_match(log: &str) -> Option<char> {
+    let mut competitors = log.bytes().filter(|&b| is_competitor(b));
+    let first = competitors.next()?;
+    competitors.all(|b| b == first).then_some(first as char)
+}
+
+/// The finest (`t == 1`) and coarsest (`s == 1`) valid outcomes, when they
+/// exist, checked directly without enumerating the candidates in between.
+pub fn extreme_outcomes(log: &str) -> (Option<Outcome>, Option<Outcome>) {
+    let stream = stream_of(log);
+    let finest = stream
+        .sweep_at(1)
+        .map(|(s, side)| Outcome::new(s, 1, winner_char(side, 'A', 'B')));
+    let coarsest = [Side::First, Side::Second].into_iter().find_map(|side| {
+        let t = stream.points(side);
+        (t > 0 && stream.is_sweep(1, t, side))
+            .then(|| Outcome::new(1, t, winner_char(side, 'A', 'B')))
+    });
+    (finest, coarsest)
+}
+
+/// Whether swapping `s` and `t` maps the log's outcome set onto itself.
+///
+/// Every divisor pair of `n` reads an all-identical log of length `n`, so
+/// those logs are always symmetric; mixed logs generally are not.
+pub fn has_swap_symmetry(log: &str) -> bool {
+    let outcomes = analyze_sales_sprints(log);
+    outcomes.iter().all(|o| {
+        outcomes
+            .binary_search(&Outcome::new(o.t, o.s, o.winner))
+            .is_ok()
+    })
+}
+
+/// Checks that every outcome of the log consumes exactly `s * t` of its
+/// winner's points, which is all of [`competitor_count`] when only one
+/// competitor appears.
+///
+/// # Panics
+///
+/// If some outcome's `s * t` differs from its winner's point count.
+pub fn assert_length_invariant(log: &str) {
+    for o in analyze_sales_sprints(log) {
+        let points = log.chars().filter(|&c| c == o.winner).count();
+        assert_eq!(
+            o.s * o.t,
+            points,
+            "outcome {:?} should consume all {} of its winner's points",
+            o,
+            points
+        );
+    }
+}
+
+/// The winner of each sprint at `t == 1`, where every competitor character is
+/// a sprint of its own, as a string; `None` if the log has no valid outcome.
+///
+/// That is the cleaned log itself. The `t == 1` reading need not be valid
+/// (`"AABBB"` only reads as `(1, 3, 'B')`), it is just the finest split.
+pub fn sprint_winner_string(log: &str) -> Option<String> {
+    if analyze_sales_sprints(log).is_empty() {
+        return None;
+    }
+    Some(clean_log(log).into_iter().map(char::from).collect())
+}
+
+/// The outcomes won by `player`, found by only simulating that side's
+/// candidates.
+///
+/// The winner takes the last sprint and so scores the log's last point; when
+/// `player` didn't, or isn't `'A'` or `'B'`, nothing is simulated at all.
+pub fn outcomes_won_by(log: &str, player: char) -> Vec<Outcome> {
+    let Some(side) = u8::try_from(player).ok().and_then(classify) else {
+        return Vec::new();
+    };
+    let stream = stream_of(log);
+    if stream.positions(side).last() != stream.len().checked_sub(1).as_ref() {
+        return Vec::new();
+    }
+    divisor_pairs(stream.points(side))
+        .into_iter()
+        .filter(|&(s, t)| stream.is_sweep(s, t, side))
+        .map(|(s, t)| Outcome::new(s, t, player))
+        .collect()
+}
+
+/// The outcomes not won by `expected_winner`.
+pub fn upsets_against(log: &str, expected_winner: char) -> Vec<Outcome> {
+    let mut outcomes = analyze_sales_sprints(log);
+    outcomes.retain(|o| o.winner != expected_winner);
+    outcomes
+}
+
+/// Jaccard index of the two logs' `(s, t)` sets, whoever won: 1.0 for the
+/// same structure (including two logs with no outcome at all) and 0.0 when
+/// no `(s, t)` is shared.
+pub fn structure_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (analyze_sales_sprints(a), analyze_sales_sprints(b));
+    let shared = common_structures(&a, &b).len();
+    let union = a.len() + b.len() - shared;
+    if union == 0 {
+        return 1.0;
+    }
+    shared as f64 / union as f64
+}
+
+/// The middle outcome in sorted order, the lower of the two middles for an
+/// even count; `None` if the log has no outcome.
+pub fn median_outcome(log: &str) -> Option<Outcome> {
+    let outcomes = analyze_sales_sprints(log);
+    outcomes.get(outcomes.len().checked_sub(1)? / 2).copied()
+}
+
+/// The winners reachable at each valid sprint count `s`.
+pub fn winners_per_sprint_count(log: &str) -> BTreeMap<usize, HashSet<char>> {
+    let mut winners: BTreeMap<usize, HashSet<char>> = BTreeMap::new();
+    visit_sweeps(&stream_of(log), 'A', 'B', |o| {
+        winners.entry(o.s).or_default().insert(o.winner);
+    });
+    winners
+}
+
+/// The distinct winners across all valid outcomes of the log.
+pub fn possible_winners(log: &str) -> BTreeSet<char> {
+    AnalysisConfig::default().possible_winners(log)
+}
+
+/// Whether `possible_winners(log)` has more than one entry.
+///
+/// Under the default rules this never holds: every sprint of an outcome is
+/// won by its winner, so the last competitor character always belongs to the
+/// winner. Use [`AnalysisConfig::is_ambiguous`] with
+/// [`Rule::Majority`](crate::Rule::Majority) to find genuinely ambiguous logs.
+pub fn is_ambiguous(log: &str) -> bool {
+    possible_winners(log).len() > 1
+}
+
+/// [`analyze_sales_sprints`] ordered by winner, then `s`, then `t`, so each
+/// competitor's readings sit together.
+pub fn analyze_sorted_by_winner(log: &str) -> Vec<Outcome> {
+    let mut outcomes = analyze_sales_sprints(log);
+    outcomes.sort_by_key(|o| (o.winner, o.s, o.t));
+    outcomes
+}
+
+/// [`analyze_sales_sprints`] wrapped in [`Outcomes`].
+pub fn analyze_outcomes(log: &str) -> Outcomes {
+    analyze_sales_sprints(log).into()
+}
+
+/// [`analyze_sales_sprints`], with a log that has no points told apart from
+/// one whose points admit no decomposition.
+pub fn analyze_classified(log: &str) -> AnalysisResult {
+    if competitor_count(log) == 0 {
+        return AnalysisResult::Empty;
+    }
+    let outcomes = analyze_sales_sprints(log);
+    if outcomes.is_empty() {
+        AnalysisResult::NoValidDecomposition
+    } else {
+        AnalysisResult::Outcomes(outcomes)
+    }
+}
+
+/// The outcomes of [`analyze_sales_sprints`], validated from the end of the
+/// cleaned stream backwards.
+///
+/// Mirroring the log is not enough (`"AABBB"` reversed has no outcome), but
+/// every sprint of a valid outcome closes on one of the winner's points, so
+/// its boundaries can be recovered from the end just as well as from the
+/// start. Debug builds run this as a consistency check on every forward pass.
+pub fn analyze_reverse(log: &str) -> Vec<Outcome> {
+    reverse_sweep_outcomes(&stream_of(log), 'A', 'B')
+}
+
+/// Runs the sprint analysis over whole tokens, for logs that name the
+/// competitors (`"Team1"`, `"Team2"`) rather than using single characters.
+/// Tokens matching neither name are ignored.
+pub fn analyze_tokens(tokens: &[&str], first: &str, second: &str) -> Vec<GenericOutcome<String>> {
+    let stream = Stream::from_sides(tokens.iter().filter_map(|&token| {
+        if token == first {
+            Some(Side::First)
+        } else if token == second {
+            Some(Side::Second)
+        } else {
+            None
+        }
+    }));
+    let mut outcomes = Vec::new();
+    visit_sweep_sides(&stream, |s, t, side| {
+        let winner = match side {
+            Side::First => first,
+            Side::Second => second,
+        };
+        outcomes.push(GenericOutcome {
+            s,
+            t,
+            winner: winner.to_string(),
+        });
+    });
+    outcomes.sort();
+    outcomes
+}
@@ -0,0 +1,43 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::analysis::{analyze_sales_sprints_bytes, clean_log, stream_of, visit_sweeps};
+use crate::outcome::Outcome;
+
+/// [`analyze_sales_sprints`](crate::analyze_sales_sprints) for each log, in
+/// order, analyzing each distinct cleaned log only once. Logs that differ
+/// only in ignored characters share one result.
+pub fn dedup_batch(logs: &[&str]) -> Vec<Vec<Outcome>> {
+    dedup_batch_with(logs, analyze_sales_sprints_bytes)
+}
+
+/// [`dedup_batch`] with the analysis supplied by the caller, who is handed
+/// each distinct cleaned log once.
+pub fn dedup_batch_with<F>(logs: &[&str], mut analyze: F) -> Vec<Vec<Outcome>>
+where
+    F: FnMut(&[u8]) -> Vec<Outcome>,
+{
+    let mut cache: HashMap<Vec<u8>, Vec<Outcome>> = HashMap::new();
+    logs.iter()
+        .map(|log| {
+            cache
+                .entry(clean_log(log))
+                .or_insert_with_key(|cleaned| analyze(cleaned))
+                .clone()
+        })
+        .collect()
+}
+
+/// The most common `t` across every outcome of every log, the smallest one on
+/// a tie; `None` if no log has an outcome.
+pub fn modal_target(logs: &[&str]) -> Option<usize> {
+    let mut counts = BTreeMap::new();
+    for log in logs {
+        visit_sweeps(&stream_of(log), 'A', 'B', |o| {
+            *counts.entry(o.t).or_insert(0) += 1
+        });
+    }
+    counts
+        .into_iter()
+        .max_by(|(t1, n1), (t2, n2)| n1.cmp(n2).then(t2.cmp(t1)))
+        .map(|(t, _)| t)
+}
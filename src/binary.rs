@@ -0,0 +1,85 @@
+//! Compact little-endian encoding for caching outcome lists on disk.
+//!
+//! Layout: a `u32` outcome count, then per outcome `s` and `t` as `u32`
+//! followed by the winner as one ASCII byte (9 bytes each).
+
+use crate::error::SprintError;
+use crate::outcome::Outcome;
+
+const HEADER: usize = 4;
+const RECORD: usize = 9;
+
+/// Encodes outcomes in the binary layout described in the module docs.
+///
+/// Winners from [`analyze_chars`](crate::analyze_chars) or
+/// [`analyze_utf16`](crate::analyze_utf16) need not be ASCII; the first one
+/// that isn't is rejected with [`SprintError::NonAsciiWinner`]. An outcome
+/// count, `s` or `t` too large for its `u32` field is rejected with
+/// [`SprintError::FieldOutOfRange`].
+pub fn serialize_binary(outcomes: &[Outcome]) -> Result<Vec<u8>, SprintError> {
+    let field = |field, index, value: usize| {
+        u32::try_from(value).map_err(|_| SprintError::FieldOutOfRange {
+            field,
+            index,
+            value,
+        })
+    };
+    let count = field("outcome count", None, outcomes.len())?;
+    let mut bytes = Vec::with_capacity(HEADER + RECORD * outcomes.len());
+    bytes.extend_from_slice(&count.to_le_bytes());
+    for (index, o) in outcomes.iter().enumerate() {
+        let s = field("s", Some(index), o.s)?;
+        let t = field("t", Some(index), o.t)?;
+        let winner = u8::try_from(o.winner).ok().filter(u8::is_ascii).ok_or(
+            SprintError::NonAsciiWinner {
+                index,
+                winner: o.winner,
+            },
+        )?;
+        bytes.extend_from_slice(&s.to_le_bytes());
+        bytes.extend_from_slice(&t.to_le_bytes());
+        bytes.push(winner);
+    }
+    Ok(bytes)
+}
+
+/// Decodes what [`serialize_binary`] produced.
+///
+/// Input whose length disagrees with its count header is rejected with
+/// [`SprintError::BinaryLength`], and a non-ASCII winner byte with
+/// [`SprintError::InvalidCharacter`] at its offset.
+pub fn deserialize_binary(bytes: &[u8]) -> Result<Vec<Outcome>, SprintError> {
+    let header: [u8; HEADER] =
+        bytes
+            .get(..HEADER)
+            .and_then(|h| h.try_into().ok())
+            .ok_or(SprintError::BinaryLength {
+                expected: HEADER,
+                actual: bytes.len(),
+            })?;
+    let count = u32::from_le_bytes(header) as usize;
+    let expected = RECORD
+        .checked_mul(count)
+        .and_then(|n| n.checked_add(HEADER))
+        .unwrap_or(usize::MAX);
+    if bytes.len() != expected {
+        return Err(SprintError::BinaryLength {
+            expected,
+            actual: bytes.len(),
+        });
+    }
+    let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap()) as usize;
+    (0..count)
+        .map(|k| {
+            let at = HEADER + RECORD * k;
+            let winner = bytes[at + 8];
+            if !winner.is_ascii() {
+                return Err(SprintError::InvalidCharacter {
+                    index: at + 8,
+                    ch: winner as char,
+                });
+            }
+            Ok(Outcome::new(u32_at(at), u32_at(at + 4), winner as char))
+        })
+        .collect()
+}
@@ -0,0 +1,97 @@
+use crate::analysis::{stream_of, winner_char};
+
+/// Point-by-point feedback on a proposed `(s, t, winner)` reading, from
+/// [`check_decomposition`]. Each check comes with a message explaining it,
+/// whether it passed or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecompositionCheck {
+    /// `s * t` equals the number of points `winner` scored.
+    pub length_ok: bool,
+    pub length_message: String,
+    /// Target `t` splits the log into exactly `s` complete sprints, the last
+    /// closing on the log's final point, whoever won them.
+    pub simulation_ok: bool,
+    pub simulation_message: String,
+    /// Every sprint of target `t` is won by `winner`.
+    pub winner_ok: bool,
+    pub winner_message: String,
+}
+
+impl DecompositionCheck {
+    /// Whether every check passed, i.e. the reading is one of
+    /// [`analyze_sales_sprints`](crate::analyze_sales_sprints)'s outcomes.
+    pub fn is_valid(&self) -> bool {
+        self.length_ok && self.simulation_ok && self.winner_ok
+    }
+}
+
+/// Checks a proposed reading of the log one condition at a time, so a wrong
+/// answer can be told apart from a nearly right one.
+pub fn check_decomposition(log: &str, s: usize, t: usize, winner: char) -> DecompositionCheck {
+    let points = log.chars().filter(|&c| c == winner).count();
+    let product = s.checked_mul(t);
+    let length_ok = product == Some(points);
+    let length_message = match product {
+        Some(product) => format!(
+            "s * t = {} {} the {} points {:?} scored",
+            product,
+            if length_ok {
+                "matches"
+            } else {
+                "does not match"
+            },
+            points,
+            winner
+        ),
+        None => format!(
+            "s * t overflows, so it cannot match the {} points {:?} scored",
+            points, winner
+        ),
+    };
+
+    let stream = stream_of(log);
+    let sprints: Vec<_> = if t == 0 {
+        Vec::new()
+    } else {
+        stream.sprints(t).collect()
+    };
+    let end = sprints.last().map_or(0, |sprint| sprint.end);
+    let simulation_ok = t > 0 && sprints.len() == s && end == stream.len();
+    let simulation_message = if t == 0 {
+        "a target of 0 never completes a sprint".to_string()
+    } else if end != stream.len() {
+        format!(
+            "target {} leaves {} points after the last complete sprint",
+            t,
+            stream.len() - end
+        )
+    } else if sprints.len() != s {
+        format!("target {} gives {} sprints, not {}", t, sprints.len(), s)
+    } else {
+        format!("target {} gives exactly {} sprints", t, s)
+    };
+
+    let lost = sprints
+        .iter()
+        .position(|sprint| winner_char(sprint.winner, 'A', 'B') != winner);
+    let winner_ok = !sprints.is_empty() && lost.is_none();
+    let winner_message = match lost {
+        _ if sprints.is_empty() => "no sprint was completed".to_string(),
+        Some(k) => format!(
+            "sprint {} is won by {:?}, not {:?}",
+            k + 1,
+            winner_char(sprints[k].winner, 'A', 'B'),
+            winner
+        ),
+        None => format!("every sprint is won by {:?}", winner),
+    };
+
+    DecompositionCheck {
+        length_ok,
+        length_message,
+        simulation_ok,
+        simulation_message,
+        winner_ok,
+        winner_message,
+    }
+}
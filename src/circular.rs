@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+
+use crate::analysis::{classify, winner_char};
+use crate::divisors::divisor_pairs;
+use crate::outcome::Outcome;
+use crate::stream::{Side, Stream};
+
+/// Every `(s, t, winner)` reading of the cleaned log taken as a ring, each
+/// with the smallest rotation that gives it, sorted by outcome.
+///
+/// Rotation `r` reads the cleaned stream from index `r` to the end and then
+/// wraps around to index `r - 1`; an outcome is valid at `r` when that
+/// reading is exactly `s` sprints of target `t`, all won by `winner`.
+/// Rotation 0 is the log as written, so every outcome of
+/// [`analyze_sales_sprints`](crate::analyze_sales_sprints) appears with
+/// offset 0. A sweep has to end on one of the winner's points, so each
+/// rotation only checks the side that scored just before it, but every
+/// rotation re-indexes the whole ring: expect quadratic time in the cleaned
+/// length.
+pub fn analyze_circular(log: &str) -> Vec<(Outcome, usize)> {
+    let sides: Vec<Side> = log.bytes().filter_map(classify).collect();
+    let n = sides.len();
+    let mut offsets = BTreeMap::new();
+    for r in 0..n {
+        let side = sides[(r + n - 1) % n];
+        let stream = Stream::from_sides(sides[r..].iter().chain(&sides[..r]).copied());
+        for (s, t) in divisor_pairs(stream.points(side)) {
+            if stream.is_sweep(s, t, side) {
+                offsets
+                    .entry(Outcome::new(s, t, winner_char(side, 'A', 'B')))
+                    .or_insert(r);
+            }
+        }
+    }
+    offsets.into_iter().collect()
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeSet;
+
+use crate::analysis::{stream_of, visit_sweep_sides, winner_char};
+use crate::error::SprintError;
+use crate::outcome::Outcome;
+use crate::stream::{Side, Stream};
+
+/// How the overall winner of a decomposition is decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rule {
+    /// The match ends the moment a side has won `s` sprints, so a valid
+    /// reading of exactly `s` sprints is one side winning all of them.
+    #[default]
+    FirstToS,
+    /// All `s` sprints are played out and the side that won strictly more of
+    /// them takes the match.
+    Majority,
+}
+
+/// Settings for a configurable analysis run.
+///
+/// The default reproduces [`analyze_sales_sprints`](crate::analyze_sales_sprints):
+/// ASCII `'A'` and `'B'` as competitors, every other character ignored, the
+/// [`Rule::FirstToS`] rule, and any target of at least 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalysisConfig {
+    pub first: char,
+    pub second: char,
+    pub rule: Rule,
+    pub min_t: usize,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        AnalysisConfig {
+            first: 'A',
+            second: 'B',
+            rule: Rule::FirstToS,
+            min_t: 1,
+        }
+    }
+}
+
+impl AnalysisConfig {
+    /// Every valid outcome of the log under this configuration, sorted by `s`
+    /// then `t`.
+    pub fn analyze(&self, log: &str) -> Vec<Outcome> {
+        let stream = Stream::from_sides(log.chars().filter_map(|c| {
+            if c == self.first {
+                Some(Side::First)
+            } else if c == self.second {
+                Some(Side::Second)
+            } else {
+                None
+            }
+        }));
+        let min_t = self.min_t.max(1);
+        let mut outcomes = Vec::new();
+        let push = |s, t, side| {
+            if t >= min_t {
+                outcomes.push(Outcome::new(
+                    s,
+                    t,
+                    winner_char(side, self.first, self.second),
+                ));
+            }
+        };
+        match self.rule {
+            Rule::FirstToS => visit_sweep_sides(&stream, push),
+            Rule::Majority => visit_majorities(&stream, min_t, push),
+        }
+        outcomes.sort();
+        outcomes
+    }
+
+    /// The distinct winners across all valid outcomes of the log.
+    pub fn possible_winners(&self, log: &str) -> BTreeSet<char> {
+        self.analyze(log).into_iter().map(|o| o.winner).collect()
+    }
+
+    /// Whether different `(s, t)` readings of the log crown different winners.
+    pub fn is_ambiguous(&self, log: &str) -> bool {
+        self.possible_winners(log).len() > 1
+    }
+
+    /// Like [`AnalysisConfig::analyze`], but any character other than the two
+    /// competitors is an error instead of being skipped.
+    ///
+    /// The reported index is a byte offset, so `&log[index..]` starts at the
+    /// offending character even after multi-byte text.
+    pub fn analyze_strict(&self, log: &str) -> Result<Vec<Outcome>, SprintError> {
+        if let Some((index, ch)) = log
+            .char_indices()
+            .find(|&(_, c)| c != self.first && c != self.second)
+        {
+            return Err(SprintError::InvalidCharacter { index, ch });
+        }
+        Ok(self.analyze(log))
+    }
+}
+
+/// Analyzes a log that may open with a `#players:X,Y` line naming the two
+/// competitors; without one, `'A'` and `'B'` are used as usual.
+///
+/// A first line starting with `#players` that doesn't name two distinct
+/// single characters is rejected with [`SprintError::InvalidHeader`].
+pub fn analyze_with_header(input: &str) -> Result<Vec<Outcome>, SprintError> {
+    if !input.starts_with("#players") {
+        return Ok(AnalysisConfig::default().analyze(input));
+    }
+    let (header, body) = input.split_once('\n').unwrap_or((input, ""));
+    let header = header.trim_end_matches('\r');
+    let invalid = || SprintError::InvalidHeader {
+        header: header.to_string(),
+    };
+    let (first, second) = header
+        .strip_prefix("#players:")
+        .and_then(|players| players.split_once(','))
+        .ok_or_else(invalid)?;
+    let single = |name: &str| {
+        let mut chars = name.chars();
+        chars.next().filter(|_| chars.next().is_none())
+    };
+    match (single(first), single(second)) {
+        (Some(first), Some(second)) if first != second => Ok(AnalysisConfig {
+            first,
+            second,
+            ..AnalysisConfig::default()
+        }
+        .analyze(body)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Analyzes two matches interleaved in one log, each pair's characters read
+/// on their own with everything else, the other pair included, ignored.
+pub fn analyze_dual_pairs(
+    log: &str,
+    pair1: (char, char),
+    pair2: (char, char),
+) -> (Vec<Outcome>, Vec<Outcome>) {
+    let analyze = |(first, second): (char, char)| {
+        AnalysisConfig {
+            first,
+            second,
+            ..AnalysisConfig::default()
+        }
+        .analyze(log)
+    };
+    (analyze(pair1), analyze(pair2))
+}
+
+/// Calls `visit` for every target from `min_t` whose sprints cover the whole
+/// stream and are won mostly by one side.
+fn visit_majorities<F: FnMut(usize, usize, Side)>(stream: &Stream, min_t: usize, mut visit: F) {
+    for t in min_t..=stream.len() {
+        let (mut first, mut second, mut end) = (0, 0, 0);
+        for sprint in stream.sprints(t) {
+            match sprint.winner {
+                Side::First => first += 1,
+                Side::Second => second += 1,
+            }
+            end = sprint.end;
+        }
+        if end != stream.len() || first == second {
+            continue;
+        }
+        let side = if first > second {
+            Side::First
+        } else {
+            Side::Second
+        };
+        visit(first + second, t, side);
+    }
+}
+
+/// What to do with a reading whose sprint wins end up level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TiePolicy {
+    /// Drop tied readings, as [`analyze_sales_sprints`](crate::analyze_sales_sprints) does.
+    #[default]
+    Exclude,
+    /// Award a tied reading to whoever won its last sprint.
+    LastSprintWins,
+    /// Award a tied reading to whoever won its first sprint.
+    FirstSprintWins,
+}
+
+/// [`analyze_sales_sprints`](crate::analyze_sales_sprints) plus, depending on
+/// `policy`, readings where both sides won the same number of sprints.
+///
+/// Only readings that consume the whole log are considered: a target whose
+/// last sprint is left incomplete is invalid whatever the policy. Readings
+/// with an outright but non-sweeping winner (say 3 sprints to 2) stay
+/// invalid as well; only a level score is resolved by the policy.
+pub fn analyze_with_tie_policy(log: &str, policy: TiePolicy) -> Vec<Outcome> {
+    let stream = stream_of(log);
+    let mut outcomes = Vec::new();
+    for t in 1..=stream.len() {
+        let (mut first_wins, mut second_wins, mut end) = (0, 0, 0);
+        let (mut opener, mut closer) = (None, None);
+        for sprint in stream.sprints(t) {
+            match sprint.winner {
+                Side::First => first_wins += 1,
+                Side::Second => second_wins += 1,
+            }
+            opener.get_or_insert(sprint.winner);
+            closer = Some(sprint.winner);
+            end = sprint.end;
+        }
+        let (Some(opener), Some(closer)) = (opener, closer) else {
+            continue;
+        };
+        if end != stream.len() {
+            continue;
+        }
+        let s = first_wins + second_wins;
+        let winner = if first_wins == 0 || second_wins == 0 {
+            Some(closer)
+        } else if first_wins == second_wins {
+            match policy {
+                TiePolicy::Exclude => None,
+                TiePolicy::LastSprintWins => Some(closer),
+                TiePolicy::FirstSprintWins => Some(opener),
+            }
+        } else {
+            None
+        };
+        if let Some(side) = winner {
+            outcomes.push(Outcome::new(s, t, winner_char(side, 'A', 'B')));
+        }
+    }
+    outcomes.sort();
+    outcomes
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::analysis::{stream_of, sweep_outcomes, winner_char};
+use crate::divisors::divisor_pairs;
+use crate::outcome::Outcome;
+use crate::stream::{Side, Stream};
+
+/// How one-sided a match was, by sprint wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchCategory {
+    /// The loser won no sprint.
+    Sweep,
+    /// The loser won at least one sprint and the margin is at least 2.
+    Decisive,
+    /// The loser won at least one sprint and the margin is 1.
+    Close,
+}
+
+/// An outcome together with how many sprints each side won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DetailedOutcome {
+    pub outcome: Outcome,
+    pub a_sprints: usize,
+    pub b_sprints: usize,
+}
+
+impl DetailedOutcome {
+    /// Difference between the winner's and the loser's sprint wins.
+    pub fn margin(&self) -> usize {
+        self.a_sprints.abs_diff(self.b_sprints)
+    }
+
+    /// Share of the `s` sprints taken by the winner: 1.0 for a sweep, near
+    /// 0.5 for a narrow win.
+    pub fn win_ratio(&self) -> f64 {
+        let winner_sprints = match self.outcome.winner {
+            'B' => self.b_sprints,
+            _ => self.a_sprints,
+        };
+        winner_sprints as f64 / self.outcome.s as f64
+    }
+
+    /// The [`MatchCategory`] of the sprint score. A level score, which no
+    /// valid outcome has, counts as [`MatchCategory::Close`].
+    pub fn category(&self) -> MatchCategory {
+        if self.a_sprints == 0 || self.b_sprints == 0 {
+            MatchCategory::Sweep
+        } else if self.margin() >= 2 {
+            MatchCategory::Decisive
+        } else {
+            MatchCategory::Close
+        }
+    }
+}
+
+/// Replays the sprints of `outcome` over the stream to tally each side's wins.
+pub(crate) fn detail(stream: &Stream, outcome: Outcome) -> DetailedOutcome {
+    let (mut a_sprints, mut b_sprints) = (0, 0);
+    for sprint in stream.sprints(outcome.t) {
+        match sprint.winner {
+            Side::First => a_sprints += 1,
+            Side::Second => b_sprints += 1,
+        }
+    }
+    DetailedOutcome {
+        outcome,
+        a_sprints,
+        b_sprints,
+    }
+}
+
+/// [`analyze_sales_sprints`](crate::analyze_sales_sprints) with each
+/// outcome's sprint breakdown.
+pub fn analyze_sales_sprints_detailed(log: &str) -> Vec<DetailedOutcome> {
+    let stream = stream_of(log);
+    sweep_outcomes(&stream, 'A', 'B')
+        .into_iter()
+        .map(|o| detail(&stream, o))
+        .collect()
+}
+
+/// [`analyze_sales_sprints`](crate::analyze_sales_sprints) with the cleaned
+/// index range of each sprint. The ranges of an outcome are contiguous and
+/// together cover the whole cleaned log.
+pub fn analyze_with_ranges(log: &str) -> Vec<(Outcome, Vec<Range<usize>>)> {
+    let stream = stream_of(log);
+    sweep_outcomes(&stream, 'A', 'B')
+        .into_iter()
+        .map(|o| {
+            let mut start = 0;
+            let ranges = stream
+                .sprints(o.t)
+                .map(|sprint| {
+                    let range = start..sprint.end;
+                    start = sprint.end;
+                    range
+                })
+                .collect();
+            (o, ranges)
+        })
+        .collect()
+}
+
+/// The detailed outcome with the widest sprint-win margin, the larger `s` on
+/// a tie; `None` if the log has no outcome.
+pub fn most_dominant(log: &str) -> Option<DetailedOutcome> {
+    analyze_sales_sprints_detailed(log)
+        .into_iter()
+        .max_by_key(|d| (d.margin(), d.outcome.s))
+}
+
+/// The mean [`DetailedOutcome::win_ratio`] over the log's detailed outcomes,
+/// or 0.0 if it has none.
+///
+/// Every default outcome is a sweep, so any log with an outcome scores 1.0;
+/// lower scores only separate logs that admit no reading at all, such as a
+/// balanced one.
+pub fn decisiveness(log: &str) -> f64 {
+    let detailed = analyze_sales_sprints_detailed(log);
+    if detailed.is_empty() {
+        return 0.0;
+    }
+    detailed.iter().map(DetailedOutcome::win_ratio).sum::<f64>() / detailed.len() as f64
+}
+
+/// [`analyze_sales_sprints_detailed`] produced lazily, in the same order.
+///
+/// The candidates are sorted up front, which takes no simulation, and each
+/// one is only validated when the iterator reaches it. A validated sweep
+/// already says who won every sprint, so no second replay is needed for the
+/// breakdown.
+pub fn analyze_detailed_iter(log: &str) -> impl Iterator<Item = DetailedOutcome> {
+    let stream = stream_of(log);
+    let mut candidates: Vec<(Outcome, Side)> = [Side::First, Side::Second]
+        .into_iter()
+        .flat_map(|side| {
+            divisor_pairs(stream.points(side))
+                .into_iter()
+                .map(move |(s, t)| (Outcome::new(s, t, winner_char(side, 'A', 'B')), side))
+        })
+        .collect();
+    candidates.sort_by_key(|&(outcome, _)| outcome);
+    candidates.into_iter().filter_map(move |(outcome, side)| {
+        if !stream.is_sweep(outcome.s, outcome.t, side) {
+            return None;
+        }
+        let (a_sprints, b_sprints) = match side {
+            Side::First => (outcome.s, 0),
+            Side::Second => (0, outcome.s),
+        };
+        Some(DetailedOutcome {
+            outcome,
+            a_sprints,
+            b_sprints,
+        })
+    })
+}
+
+/// How many of the log's outcomes have each sprint-win margin.
+///
+/// Under the default rules the winner takes every sprint, so each outcome's
+/// margin is its `s`.
+pub fn margin_histogram(log: &str) -> BTreeMap<usize, usize> {
+    let mut histogram = BTreeMap::new();
+    for detailed in analyze_sales_sprints_detailed(log) {
+        *histogram.entry(detailed.margin()).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// Sprint wins of each side summed over every detailed outcome, as
+/// `(a_sprints, b_sprints)`.
+pub fn weighted_winner_totals(log: &str) -> (usize, usize) {
+    analyze_sales_sprints_detailed(log)
+        .iter()
+        .fold((0, 0), |(a, b), d| (a + d.a_sprints, b + d.b_sprints))
+}
@@ -0,0 +1,89 @@
+/// All `(s, t)` pairs with `s * t == n`, sorted by `s`.
+pub fn divisor_pairs(n: usize) -> Vec<(usize, usize)> {
+    let mut low = Vec::new();
+    let mut high = Vec::new();
+    let mut d = 1;
+    while d <= n / d {
+        if n.is_multiple_of(d) {
+            low.push((d, n / d));
+            if d != n / d {
+                high.push((n / d, d));
+            }
+        }
+        d += 1;
+    }
+    low.extend(high.into_iter().rev());
+    low
+}
+
+/// Number of divisors of `n` from its prime factorization; zero for `n == 0`.
+///
+/// This is the outcome count of a log of `n` identical competitor characters,
+/// found in `O(sqrt(n))` without listing the divisors.
+pub fn divisor_count(mut n: usize) -> usize {
+    if n == 0 {
+        return 0;
+    }
+    let mut count = 1;
+    let mut p = 2;
+    while p <= n / p {
+        let mut exponent = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            exponent += 1;
+        }
+        count *= exponent + 1;
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        count *= 2;
+    }
+    count
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Deterministic Miller-Rabin; these bases cover every `u64`.
+pub(crate) fn is_prime(n: usize) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    let n = n as u64;
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let r = (n - 1).trailing_zeros();
+    let d = (n - 1) >> r;
+    'bases: for a in BASES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..r {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
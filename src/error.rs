@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// Errors reported by the analyzer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SprintError {
+    /// The same `(s, t)` was given two different winners.
+    ConflictingWinners {
+        s: usize,
+        t: usize,
+        winners: (char, char),
+    },
+    /// Strict analysis met a character that is neither competitor; `index` is
+    /// its byte offset in the log.
+    InvalidCharacter { index: usize, ch: char },
+    /// Binary outcome data was not the length its count header implies.
+    BinaryLength { expected: usize, actual: usize },
+    /// A `#players:` header did not name two distinct single characters.
+    InvalidHeader { header: String },
+    /// An outcome's winner has no one-byte binary encoding; `index` is the
+    /// outcome's position in the list.
+    NonAsciiWinner { index: usize, winner: char },
+    /// A value too large for its `u32` binary field: the outcome count, or
+    /// the `s` or `t` of the outcome at `index`.
+    FieldOutOfRange {
+        field: &'static str,
+        index: Option<usize>,
+        value: usize,
+    },
+    /// The runs of a run-length-encoded log add up to more than `usize::MAX`
+    /// competitor characters; `run` is where the total overflowed.
+    RunLengthOverflow { run: usize },
+}
+
+impl fmt::Display for SprintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SprintError::ConflictingWinners { s, t, winners } => write!(
+                f,
+                "outcome (s={}, t={}) has conflicting winners {:?} and {:?}",
+                s, t, winners.0, winners.1
+            ),
+            SprintError::InvalidCharacter { index, ch } => {
+                write!(f, "invalid character {:?} at byte {}", ch, index)
+            }
+            SprintError::BinaryLength { expected, actual } => write!(
+                f,
+                "binary outcome data should be {} bytes, found {}",
+                expected, actual
+            ),
+            SprintError::InvalidHeader { header } => {
+                write!(f, "invalid players header {:?}", header)
+            }
+            SprintError::NonAsciiWinner { index, winner } => write!(
+                f,
+                "outcome {} has non-ASCII winner {:?}, which the binary layout cannot hold",
+                index, winner
+            ),
+            SprintError::FieldOutOfRange {
+                field,
+                index: Some(index),
+                value,
+            } => write!(
+                f,
+                "outcome {} has {} = {}, which does not fit in a u32",
+                index, field, value
+            ),
+            SprintError::FieldOutOfRange {
+                field,
+                index: None,
+                value,
+            } => write!(f, "{} {} does not fit in a u32", field, value),
+            SprintError::RunLengthOverflow { run } => {
+                write!(f, "run {} takes the log length past usize::MAX", run)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SprintError {}
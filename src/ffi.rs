@@ -0,0 +1,49 @@
+//! C ABI over [`analyze_flat`](crate::analyze_flat), enabled by the `ffi`
+//! feature.
+//!
+//! Results use the flat triple-stride layout and are owned by Rust: every
+//! array returned by [`sssla_analyze`] must be released with [`sssla_free`].
+
+use std::ffi::{c_char, CStr};
+
+use crate::analysis::analyze_sales_sprints_bytes;
+use crate::flat::flatten;
+
+/// Analyzes a NUL-terminated log and returns its outcomes as a heap array of
+/// `3 * n` `u32`s, storing that length in `*out_len`.
+///
+/// The log need not be UTF-8; bytes other than `'A'` and `'B'` are ignored.
+/// A null `log` yields a null array and a length of 0.
+///
+/// # Safety
+///
+/// `log` must be null or point to a NUL-terminated string, and `out_len`
+/// must be null or valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn sssla_analyze(log: *const c_char, out_len: *mut usize) -> *mut u32 {
+    let (ptr, len) = if log.is_null() {
+        (std::ptr::null_mut(), 0)
+    } else {
+        let bytes = CStr::from_ptr(log).to_bytes();
+        let flat = flatten(&analyze_sales_sprints_bytes(bytes)).into_boxed_slice();
+        let len = flat.len();
+        (Box::into_raw(flat) as *mut u32, len)
+    };
+    if !out_len.is_null() {
+        *out_len = len;
+    }
+    ptr
+}
+
+/// Releases an array returned by [`sssla_analyze`]. Null is ignored.
+///
+/// # Safety
+///
+/// `ptr` and `len` must be exactly what one call to [`sssla_analyze`]
+/// returned, and the array must not be used or freed again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn sssla_free(ptr: *mut u32, len: usize) {
+    if !ptr.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)));
+    }
+}
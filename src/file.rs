@@ -0,0 +1,74 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::analyze_sales_sprints_bytes;
+use crate::outcome::Outcome;
+
+/// Reads a log file and analyzes its bytes; the file need not be UTF-8.
+pub fn analyze_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<Outcome>> {
+    Ok(analyze_sales_sprints_bytes(&std::fs::read(path)?))
+}
+
+/// [`analyze_file`] for every regular file directly inside `dir`, sorted by
+/// path. Subdirectories are skipped.
+///
+/// With the `rayon` feature the files are read and analyzed in parallel; the
+/// results come back in the same order either way.
+pub fn analyze_dir<P: AsRef<Path>>(dir: P) -> io::Result<Vec<(PathBuf, Vec<Outcome>)>> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+    let analyze = |path: PathBuf| analyze_file(&path).map(|outcomes| (path, outcomes));
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        paths.into_par_iter().map(analyze).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        paths.into_iter().map(analyze).collect()
+    }
+}
+
+/// [`analyze_file`] for async callers, behind the `tokio` feature.
+///
+/// The file is read with `tokio::fs`, and the CPU-bound analysis then runs on
+/// a `spawn_blocking` task so it never stalls the runtime's worker threads.
+#[cfg(feature = "tokio")]
+pub async fn analyze_file_async<P: AsRef<Path>>(path: P) -> io::Result<Vec<Outcome>> {
+    let bytes = tokio::fs::read(path).await?;
+    tokio::task::spawn_blocking(move || analyze_sales_sprints_bytes(&bytes))
+        .await
+        .map_err(io::Error::other)
+}
+
+/// [`analyze_file`] for a gzip-compressed log, behind the `gzip` feature.
+///
+/// The file is decompressed a buffer at a time and only the competitor
+/// characters are kept, so the uncompressed text is never held in memory.
+#[cfg(feature = "gzip")]
+pub fn analyze_gz_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<Outcome>> {
+    use std::io::BufRead;
+
+    use crate::analysis::{classify, sweep_outcomes};
+    use crate::stream::Stream;
+
+    let file = std::fs::File::open(path)?;
+    let mut reader = io::BufReader::new(flate2::read::GzDecoder::new(file));
+    let mut sides = Vec::new();
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        sides.extend(buf.iter().copied().filter_map(classify));
+        let len = buf.len();
+        reader.consume(len);
+    }
+    Ok(sweep_outcomes(&Stream::from_sides(sides), 'A', 'B'))
+}
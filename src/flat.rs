@@ -0,0 +1,27 @@
+//! Flat `u32` encoding of outcome lists for C consumers.
+//!
+//! Layout: a triple per outcome, `[s, t, winner]`, with the winner as its
+//! ASCII code, so outcome `k` occupies indices `3k..3k + 3`.
+
+use crate::analysis::analyze_sales_sprints;
+use crate::outcome::Outcome;
+
+/// [`analyze_sales_sprints`] in the triple-stride layout described in the
+/// module docs.
+///
+/// # Panics
+///
+/// If an `s` or `t` does not fit in a `u32`.
+pub fn analyze_flat(log: &str) -> Vec<u32> {
+    flatten(&analyze_sales_sprints(log))
+}
+
+pub(crate) fn flatten(outcomes: &[Outcome]) -> Vec<u32> {
+    let mut flat = Vec::with_capacity(3 * outcomes.len());
+    for o in outcomes {
+        flat.push(u32::try_from(o.s).expect("s should fit in a u32"));
+        flat.push(u32::try_from(o.t).expect("t should fit in a u32"));
+        flat.push(o.winner as u32);
+    }
+    flat
+}
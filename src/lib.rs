@@ -0,0 +1,86 @@
+//! Sales sprint log analysis.
+//!
+//! A log is a stream of characters in which every `'A'` is a point for the
+//! first competitor and every `'B'` a point for the second; anything else is
+//! ignored. A match is played as a series of sprints, and a sprint ends as soon
+//! as one side has scored `t` points in it. An outcome `(s, t, winner)` is
+//! valid when the log splits into exactly `s` sprints of target `t`, all won by
+//! `winner`, with nothing left over.
+
+mod analysis;
+mod batch;
+mod binary;
+mod check;
+mod circular;
+mod config;
+mod detailed;
+mod divisors;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod file;
+mod flat;
+mod outcome;
+#[cfg(feature = "reference")]
+pub mod reference;
+mod report;
+mod rle;
+#[cfg(feature = "simd")]
+mod simd;
+mod stats;
+mod stream;
+mod summary;
+mod sweep;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "bitvec")]
+pub use analysis::analyze_bits;
+pub use analysis::{
+    all_outcomes_for_length, analyze_allow_early, analyze_chars, analyze_classified,
+    analyze_fixed_win_target, analyze_max_sprints, analyze_min_length, analyze_multi,
+    analyze_outcomes, analyze_reach_or_exceed, analyze_reset_on_noise, analyze_reverse,
+    analyze_sales_sprints, analyze_sales_sprints_bytes, analyze_sales_sprints_strict,
+    analyze_sales_sprints_with_capacity, analyze_sliding, analyze_sorted_by_winner,
+    analyze_step_points, analyze_target_range, analyze_timed, analyze_to_channel, analyze_tokens,
+    analyze_utf16, analyze_weighted_input, analyze_with_ignored_count, analyze_with_predicate,
+    analyze_with_progress, analyze_with_table, assert_length_invariant, balance_point,
+    boundaries_agree, candidate_count, canonical_log, clean_log, clean_with_map, competitor_count,
+    extreme_outcomes, first_invalid_context, has_swap_symmetry, is_ambiguous, is_balanced,
+    is_prime_length, is_single_sprint_match, longest_run, median_outcome, outcomes_by_target,
+    outcomes_won_by, possible_winners, running_winners, sprint_sequence_hash, sprint_winner_string,
+    structure_similarity, upsets_against, winners_per_sprint_count, CharClass,
+};
+pub use batch::{dedup_batch, dedup_batch_with, modal_target};
+pub use binary::{deserialize_binary, serialize_binary};
+pub use check::{check_decomposition, DecompositionCheck};
+pub use circular::analyze_circular;
+pub use config::{
+    analyze_dual_pairs, analyze_with_header, analyze_with_tie_policy, AnalysisConfig, Rule,
+    TiePolicy,
+};
+pub use detailed::{
+    analyze_detailed_iter, analyze_sales_sprints_detailed, analyze_with_ranges, decisiveness,
+    margin_histogram, most_dominant, weighted_winner_totals, DetailedOutcome, MatchCategory,
+};
+pub use divisors::{divisor_count, divisor_pairs};
+pub use error::SprintError;
+#[cfg(feature = "tokio")]
+pub use file::analyze_file_async;
+#[cfg(feature = "gzip")]
+pub use file::analyze_gz_file;
+pub use file::{analyze_dir, analyze_file};
+pub use flat::analyze_flat;
+pub use outcome::{
+    common_structures, dedup_outcomes, dedup_outcomes_iter, AnalysisResult, GenericOutcome,
+    Outcome, Outcomes,
+};
+#[cfg(feature = "arrow")]
+pub use report::outcomes_to_arrow;
+pub use report::{detailed_outcomes_to_tsv, report, to_dot};
+pub use rle::analyze_rle;
+pub use stats::{analysis_plan, analyze_with_stats, AnalysisPlan, AnalysisStats};
+pub use summary::{
+    summarize, summarize_streaming, target_parity_counts, winner_counts, winner_entropy, Summary,
+};
+pub use sweep::{PrefixAnalyzer, TargetSweep, TruncatableAnalyzer};
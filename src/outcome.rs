@@ -0,0 +1,264 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::Deref;
+
+use crate::analysis::{stream_of, winner_char};
+use crate::error::SprintError;
+use crate::stream::Side;
+
+/// A valid way to read a log: `s` sprints of target `t`, all won by `winner`.
+///
+/// Outcomes order by `s`, then `t`, then winner in `char` order, so `'A'`
+/// comes before `'B'`. One ruleset never gives an `(s, t)` two winners, but
+/// results merged across rulesets still sort deterministically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Outcome {
+    pub s: usize,
+    pub t: usize,
+    pub winner: char,
+}
+
+impl Outcome {
+    pub fn new(s: usize, t: usize, winner: char) -> Self {
+        Outcome { s, t, winner }
+    }
+
+    /// Whether both outcomes have the same `(s, t)`, whoever won.
+    pub fn structurally_eq(&self, other: &Outcome) -> bool {
+        (self.s, self.t) == (other.s, other.t)
+    }
+
+    /// Points the winner scored over the whole match, `s * t`. That is every
+    /// competitor character when the loser never scored. `None` when `s * t`
+    /// overflows `usize`, which only hand-built outcomes can do.
+    pub fn total_points(&self) -> Option<usize> {
+        self.s.checked_mul(self.t)
+    }
+
+    /// Points the winner needed to close each sprint, i.e. `t`.
+    pub fn points_per_sprint(&self) -> usize {
+        self.t
+    }
+
+    /// The outcome as a field map with keys `"s"`, `"t"` and `"winner"`, for
+    /// consumers that take generic string fields.
+    pub fn as_fields(&self) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("s".to_string(), self.s.to_string()),
+            ("t".to_string(), self.t.to_string()),
+            ("winner".to_string(), self.winner.to_string()),
+        ])
+    }
+
+    /// Whether this first-to-`s` reading of `log`, as returned by
+    /// [`analyze_fixed_win_target`](crate::analyze_fixed_win_target), went
+    /// to a decider: both sides on `s - 1` sprint wins of target `t` going
+    /// into the last sprint, which `winner` then took on the last point.
+    ///
+    /// Replays sprints until either side reaches `s` wins, so it is false
+    /// whenever the outcome is not such a reading of `log`. It is also false
+    /// for `s == 1`, where no sprint was played before the last one.
+    pub fn decided_in_last_sprint(&self, log: &str) -> bool {
+        if self.s < 2 || self.t == 0 {
+            return false;
+        }
+        let stream = stream_of(log);
+        let (mut first, mut second) = (0, 0);
+        for sprint in stream.sprints(self.t) {
+            let (wins, other) = match sprint.winner {
+                Side::First => (&mut first, second),
+                Side::Second => (&mut second, first),
+            };
+            *wins += 1;
+            if *wins == self.s {
+                return other == self.s - 1
+                    && sprint.end == stream.len()
+                    && winner_char(sprint.winner, 'A', 'B') == self.winner;
+            }
+        }
+        false
+    }
+
+    /// The 0-based index of the sprint of target `t` over `log` after which
+    /// `winner` went ahead on sprint wins for good, or `None` if they led from
+    /// the first sprint on.
+    ///
+    /// Replays the log as a [`Rule::Majority`](crate::Rule::Majority)
+    /// reading, the only kind where the lead can change hands. It is `None`
+    /// whenever the outcome is not such a reading of `log`:
+    /// - the replay does not give exactly `s` sprints;
+    /// - the last sprint does not end on the last point;
+    /// - `winner` is not ahead after the last sprint;
+    /// - `winner` is neither `'A'` nor `'B'`.
+    pub fn last_lead_change(&self, log: &str) -> Option<usize> {
+        let side = match self.winner {
+            'A' => Side::First,
+            'B' => Side::Second,
+            _ => return None,
+        };
+        if self.t == 0 {
+            return None;
+        }
+        let stream = stream_of(log);
+        let (mut ours, mut theirs, mut played, mut end) = (0, 0, 0, 0);
+        let mut lead_from = None;
+        for (i, sprint) in stream.sprints(self.t).enumerate() {
+            if sprint.winner == side {
+                ours += 1;
+            } else {
+                theirs += 1;
+            }
+            if ours <= theirs {
+                lead_from = None;
+            } else if lead_from.is_none() {
+                lead_from = Some(i);
+            }
+            (played, end) = (i + 1, sprint.end);
+        }
+        if played != self.s || end != stream.len() {
+            return None;
+        }
+        lead_from.filter(|&i| i > 0)
+    }
+}
+
+impl Ord for Outcome {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.s, self.t, self.winner).cmp(&(other.s, other.t, other.winner))
+    }
+}
+
+impl PartialOrd for Outcome {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// An outcome whose winner is any competitor label rather than a `char`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GenericOutcome<T> {
+    pub s: usize,
+    pub t: usize,
+    pub winner: T,
+}
+
+/// Sorts outcomes and merges exact duplicates.
+///
+/// A single log never gives one `(s, t)` two winners, so when merged results
+/// do, the inputs came from incompatible rulesets. Rather than picking one,
+/// the first such collision (in sorted order) is returned as
+/// [`SprintError::ConflictingWinners`].
+pub fn dedup_outcomes(mut outcomes: Vec<Outcome>) -> Result<Vec<Outcome>, SprintError> {
+    outcomes.sort();
+    outcomes.dedup();
+    for pair in outcomes.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if (a.s, a.t) == (b.s, b.t) {
+            return Err(SprintError::ConflictingWinners {
+                s: a.s,
+                t: a.t,
+                winners: (a.winner, b.winner),
+            });
+        }
+    }
+    Ok(outcomes)
+}
+
+/// Passes each distinct outcome through the first time it appears, in
+/// arrival order, so lazily merged streams need not be collected and sorted
+/// first.
+pub fn dedup_outcomes_iter<I: Iterator<Item = Outcome>>(iter: I) -> impl Iterator<Item = Outcome> {
+    let mut seen = HashSet::new();
+    iter.filter(move |o| seen.insert(*o))
+}
+
+/// The `(s, t)` pairs that appear in both outcome lists, whoever won them,
+/// sorted and without repeats.
+pub fn common_structures(a: &[Outcome], b: &[Outcome]) -> Vec<(usize, usize)> {
+    let in_a: BTreeSet<(usize, usize)> = a.iter().map(|o| (o.s, o.t)).collect();
+    let in_b: BTreeSet<(usize, usize)> = b.iter().map(|o| (o.s, o.t)).collect();
+    in_a.intersection(&in_b).copied().collect()
+}
+
+/// The result of [`analyze_classified`](crate::analyze_classified), telling
+/// apart the two ways a log can have no outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnalysisResult {
+    /// The log has no `'A'` or `'B'` characters at all.
+    Empty,
+    /// The log has points, but no `(s, t, winner)` splits them exactly.
+    NoValidDecomposition,
+    /// The outcomes of [`analyze_sales_sprints`](crate::analyze_sales_sprints),
+    /// never empty.
+    Outcomes(Vec<Outcome>),
+}
+
+/// A sorted outcome list with the common post-processing helpers attached.
+///
+/// Derefs to `[Outcome]` and iterates like the `Vec` it wraps, so code
+/// written against [`analyze_sales_sprints`](crate::analyze_sales_sprints)
+/// keeps working.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Outcomes(Vec<Outcome>);
+
+impl Outcomes {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The winner of each outcome, in order.
+    pub fn winners(&self) -> Vec<char> {
+        self.0.iter().map(|o| o.winner).collect()
+    }
+
+    /// The outcomes won by `winner`.
+    pub fn by_winner(&self, winner: char) -> Outcomes {
+        Outcomes(
+            self.0
+                .iter()
+                .copied()
+                .filter(|o| o.winner == winner)
+                .collect(),
+        )
+    }
+
+    pub fn into_vec(self) -> Vec<Outcome> {
+        self.0
+    }
+}
+
+impl From<Vec<Outcome>> for Outcomes {
+    fn from(outcomes: Vec<Outcome>) -> Self {
+        Outcomes(outcomes)
+    }
+}
+
+impl Deref for Outcomes {
+    type Target = [Outcome];
+
+    fn deref(&self) -> &[Outcome] {
+        &self.0
+    }
+}
+
+impl IntoIterator for Outcomes {
+    type Item = Outcome;
+    type IntoIter = std::vec::IntoIter<Outcome>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Outcomes {
+    type Item = &'a Outcome;
+    type IntoIter = std::slice::Iter<'a, Outcome>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
@@ -0,0 +1,45 @@
+//! Straightforward character-by-character implementation, kept behind the
+//! `reference` feature to cross-check the indexed one.
+
+use crate::analysis::clean_log;
+use crate::outcome::Outcome;
+use crate::stats::AnalysisStats;
+
+/// Every valid outcome, found by replaying the cleaned log for each target.
+pub fn analyze_sales_sprints(log: &str) -> Vec<Outcome> {
+    analyze_with_stats(log).0
+}
+
+/// [`analyze_sales_sprints`] with the same counters as
+/// [`crate::analyze_with_stats`]; every candidate target rescans the log.
+pub fn analyze_with_stats(log: &str) -> (Vec<Outcome>, AnalysisStats) {
+    let cleaned = clean_log(log);
+    let mut stats = AnalysisStats {
+        candidates: 0,
+        bytes_scanned: log.len(),
+    };
+    let mut outcomes = Vec::new();
+    for t in 1..=cleaned.len() {
+        stats.candidates += 1;
+        let (mut a, mut b) = (0, 0);
+        let mut winners = Vec::new();
+        for &byte in &cleaned {
+            stats.bytes_scanned += 1;
+            if byte == b'A' {
+                a += 1;
+            } else {
+                b += 1;
+            }
+            if a == t || b == t {
+                winners.push(if a == t { 'A' } else { 'B' });
+                a = 0;
+                b = 0;
+            }
+        }
+        if a + b == 0 && winners.iter().all(|&w| w == winners[0]) {
+            outcomes.push(Outcome::new(winners.len(), t, winners[0]));
+        }
+    }
+    outcomes.sort();
+    (outcomes, stats)
+}
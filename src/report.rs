@@ -0,0 +1,106 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::analysis::{analyze_sales_sprints, clean_log, stream_of_bytes, sweep_outcomes};
+use crate::detailed::DetailedOutcome;
+#[cfg(feature = "arrow")]
+use crate::outcome::Outcome;
+
+/// Writes the cleaned log on the first line, then one `s t winner` line per
+/// outcome in sorted order.
+///
+/// The log is cleaned once and the outcomes are read from that, so nothing
+/// is formatted into an intermediate `String`.
+pub fn report<W: Write>(log: &str, mut w: W) -> io::Result<()> {
+    let cleaned = clean_log(log);
+    w.write_all(&cleaned)?;
+    w.write_all(b"\n")?;
+    for o in sweep_outcomes(&stream_of_bytes(&cleaned), 'A', 'B') {
+        writeln!(w, "{} {} {}", o.s, o.t, o.winner)?;
+    }
+    Ok(())
+}
+
+/// A Graphviz DOT graph of the log's readings: the log at the root, one child
+/// node per `(s, t)` outcome, and a leaf under each naming its winner.
+pub fn to_dot(log: &str) -> String {
+    let mut dot = String::from("digraph outcomes {\n");
+    writeln!(dot, "    root [label=\"{}\"];", escape_dot(log)).unwrap();
+    for (i, o) in analyze_sales_sprints(log).into_iter().enumerate() {
+        writeln!(dot, "    o{} [label=\"s={}, t={}\"];", i, o.s, o.t).unwrap();
+        writeln!(dot, "    w{} [label=\"{}\", shape=box];", i, o.winner).unwrap();
+        writeln!(dot, "    root -> o{};", i).unwrap();
+        writeln!(dot, "    o{} -> w{};", i, i).unwrap();
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Escapes text for a double-quoted DOT label.
+fn escape_dot(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Tab-separated detailed outcomes for spreadsheet import: a header row, then
+/// one row per outcome with columns `s`, `t`, `winner`, `a_sprints`,
+/// `b_sprints` and `margin`.
+pub fn detailed_outcomes_to_tsv(outcomes: &[DetailedOutcome]) -> String {
+    let mut tsv = String::from("s\tt\twinner\ta_sprints\tb_sprints\tmargin\n");
+    for d in outcomes {
+        writeln!(
+            tsv,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            d.outcome.s,
+            d.outcome.t,
+            d.outcome.winner,
+            d.a_sprints,
+            d.b_sprints,
+            d.margin()
+        )
+        .unwrap();
+    }
+    tsv
+}
+
+/// The outcomes as an Arrow record batch with `UInt32` columns `s` and `t`
+/// and a `Utf8` column `winner`, behind the `arrow` feature.
+///
+/// # Panics
+///
+/// Panics if an `s` or `t` doesn't fit in a `u32`.
+#[cfg(feature = "arrow")]
+pub fn outcomes_to_arrow(outcomes: &[Outcome]) -> arrow::record_batch::RecordBatch {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, StringArray, UInt32Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+
+    let column = |value: fn(&Outcome) -> usize| -> ArrayRef {
+        Arc::new(UInt32Array::from_iter_values(outcomes.iter().map(|o| {
+            u32::try_from(value(o)).expect("outcome field does not fit in a u32")
+        })))
+    };
+    let winners: ArrayRef = Arc::new(StringArray::from_iter_values(
+        outcomes.iter().map(|o| o.winner.to_string()),
+    ));
+    let schema = Schema::new(vec![
+        Field::new("s", DataType::UInt32, false),
+        Field::new("t", DataType::UInt32, false),
+        Field::new("winner", DataType::Utf8, false),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![column(|o| o.s), column(|o| o.t), winners],
+    )
+    .expect("columns match the schema")
+}
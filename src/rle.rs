@@ -0,0 +1,167 @@
+use crate::analysis::{classify, winner_char};
+use crate::divisors::divisor_pairs;
+use crate::error::SprintError;
+use crate::outcome::Outcome;
+use crate::stream::{Side, Sprint};
+
+/// A cleaned log kept as runs, answering the same point-position queries as
+/// the expanded stream with a binary search over runs instead of an index
+/// per character.
+struct RunStream {
+    /// Cleaned index at which each run starts, plus the total length.
+    starts: Vec<usize>,
+    sides: Vec<Side>,
+    /// First-side points before each run, plus the total.
+    first_before: Vec<usize>,
+    /// `(points before, start)` of each run of either side, for selecting
+    /// the k-th point.
+    first_runs: Vec<(usize, usize)>,
+    second_runs: Vec<(usize, usize)>,
+}
+
+impl RunStream {
+    /// Indexes the runs, or reports the run at which the cleaned length
+    /// stops fitting in a `usize`.
+    fn new(runs: &[(char, usize)]) -> Result<Self, SprintError> {
+        let mut stream = RunStream {
+            starts: vec![0],
+            sides: Vec::new(),
+            first_before: vec![0],
+            first_runs: Vec::new(),
+            second_runs: Vec::new(),
+        };
+        let (mut first, mut second, mut len) = (0, 0, 0);
+        for (run, &(c, count)) in runs.iter().enumerate() {
+            let Some(side) = u8::try_from(c).ok().and_then(classify) else {
+                continue;
+            };
+            if count == 0 {
+                continue;
+            }
+            match side {
+                Side::First => {
+                    stream.first_runs.push((first, len));
+                    first += count;
+                }
+                Side::Second => {
+                    stream.second_runs.push((second, len));
+                    second += count;
+                }
+            }
+            len = len
+                .checked_add(count)
+                .ok_or(SprintError::RunLengthOverflow { run })?;
+            stream.sides.push(side);
+            stream.starts.push(len);
+            stream.first_before.push(first);
+        }
+        Ok(stream)
+    }
+
+    fn len(&self) -> usize {
+        *self.starts.last().unwrap()
+    }
+
+    fn points(&self, side: Side) -> usize {
+        match side {
+            Side::First => *self.first_before.last().unwrap(),
+            Side::Second => self.len() - self.points(Side::First),
+        }
+    }
+
+    /// First-side points in cleaned range `0..pos`.
+    fn first_before(&self, pos: usize) -> usize {
+        let run = self.starts.partition_point(|&start| start <= pos) - 1;
+        match self.sides.get(run) {
+            Some(Side::First) => self.first_before[run] + pos - self.starts[run],
+            _ => self.first_before[run],
+        }
+    }
+
+    /// Cleaned index of the `k`-th (0-based) point of `side`.
+    fn select(&self, side: Side, k: usize) -> Option<usize> {
+        if k >= self.points(side) {
+            return None;
+        }
+        let runs = match side {
+            Side::First => &self.first_runs,
+            Side::Second => &self.second_runs,
+        };
+        let (before, start) = runs[runs.partition_point(|&(before, _)| before <= k) - 1];
+        Some(start + k - before)
+    }
+
+    fn sprint_from(&self, start: usize, t: usize) -> Option<Sprint> {
+        let first_seen = self.first_before(start);
+        let second_seen = start - first_seen;
+        let first_end = self.select(Side::First, first_seen + t - 1).map(|i| i + 1);
+        let second_end = self
+            .select(Side::Second, second_seen + t - 1)
+            .map(|i| i + 1);
+        match (first_end, second_end) {
+            (Some(a), Some(b)) if b < a => Some(Sprint {
+                end: b,
+                winner: Side::Second,
+            }),
+            (Some(a), _) => Some(Sprint {
+                end: a,
+                winner: Side::First,
+            }),
+            (None, Some(b)) => Some(Sprint {
+                end: b,
+                winner: Side::Second,
+            }),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether the stream is exactly `s` sprints of target `t`, all won by
+    /// `side`.
+    ///
+    /// While a sprint starts inside a run of `side`, every whole `t` points
+    /// left in that run is a sprint `side` wins outright, so they are skipped
+    /// in one step. Any other sprint crosses at least one run boundary, which
+    /// keeps the cost to `O(runs · log runs)` however long the runs are.
+    fn is_sweep(&self, s: usize, t: usize, side: Side) -> bool {
+        let (mut pos, mut played) = (0, 0);
+        while played < s {
+            let run = self.starts.partition_point(|&start| start <= pos) - 1;
+            if self.sides.get(run) == Some(&side) {
+                let skip = ((self.starts[run + 1] - pos) / t).min(s - played);
+                if skip > 0 {
+                    pos += skip * t;
+                    played += skip;
+                    continue;
+                }
+            }
+            match self.sprint_from(pos, t) {
+                Some(sprint) if sprint.winner == side => pos = sprint.end,
+                _ => return false,
+            }
+            played += 1;
+        }
+        pos == self.len()
+    }
+}
+
+/// [`analyze_sales_sprints`](crate::analyze_sales_sprints) over a
+/// run-length-encoded log such as `[('A', 3), ('B', 2)]`.
+///
+/// Sprint boundaries are found by prefix arithmetic over the runs, and whole
+/// sprints inside a winner's run are skipped together, so each candidate
+/// costs `O(runs · log runs)` rather than one step per character. Runs of any
+/// other character are ignored. A log whose cleaned length doesn't fit in a
+/// `usize` is rejected with [`SprintError::RunLengthOverflow`].
+pub fn analyze_rle(runs: &[(char, usize)]) -> Result<Vec<Outcome>, SprintError> {
+    let stream = RunStream::new(runs)?;
+    let mut outcomes = Vec::new();
+    for side in [Side::First, Side::Second] {
+        for (s, t) in divisor_pairs(stream.points(side)) {
+            if stream.is_sweep(s, t, side) {
+                outcomes.push(Outcome::new(s, t, winner_char(side, 'A', 'B')));
+            }
+        }
+    }
+    outcomes.sort();
+    Ok(outcomes)
+}
@@ -0,0 +1,51 @@
+//! Word-at-a-time versions of the cleaning pass, enabled by the `simd`
+//! feature.
+//!
+//! Bytes are processed eight at a time in a `u64` (SWAR), which needs no
+//! target support; the tail that doesn't fill a word goes through the scalar
+//! path.
+
+use crate::analysis::is_competitor;
+
+const LANES: usize = 8;
+const ONES: u64 = 0x0101_0101_0101_0101;
+const LOW7: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+const HIGH: u64 = 0x8080_8080_8080_8080;
+
+/// High bit set in every byte of `word` equal to `byte`, and nowhere else.
+fn eq_mask(word: u64, byte: u8) -> u64 {
+    let x = word ^ (ONES * byte as u64);
+    !(((x & LOW7) + LOW7) | x) & HIGH
+}
+
+fn competitor_mask(word: u64) -> u64 {
+    eq_mask(word, b'A') | eq_mask(word, b'B')
+}
+
+pub(crate) fn competitor_count(bytes: &[u8]) -> usize {
+    let chunks = bytes.chunks_exact(LANES);
+    let tail = chunks
+        .remainder()
+        .iter()
+        .filter(|&&b| is_competitor(b))
+        .count();
+    chunks
+        .map(|c| competitor_mask(u64::from_le_bytes(c.try_into().unwrap())).count_ones() as usize)
+        .sum::<usize>()
+        + tail
+}
+
+pub(crate) fn clean(bytes: &[u8]) -> Vec<u8> {
+    let mut cleaned = Vec::with_capacity(bytes.len());
+    let chunks = bytes.chunks_exact(LANES);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        match competitor_mask(u64::from_le_bytes(chunk.try_into().unwrap())) {
+            0 => {}
+            HIGH => cleaned.extend_from_slice(chunk),
+            _ => cleaned.extend(chunk.iter().copied().filter(|&b| is_competitor(b))),
+        }
+    }
+    cleaned.extend(tail.iter().copied().filter(|&b| is_competitor(b)));
+    cleaned
+}
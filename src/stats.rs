@@ -0,0 +1,71 @@
+use crate::analysis::{candidate_count, competitor_count, stream_of, winner_char};
+use crate::divisors::divisor_pairs;
+use crate::outcome::Outcome;
+use crate::stream::Side;
+
+/// Work counters from an instrumented analysis run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AnalysisStats {
+    /// `(s, t, winner)` candidates that were simulated.
+    pub candidates: usize,
+    /// Input bytes read plus positions visited while simulating. Jumping
+    /// straight to a sprint's closing point counts as one position.
+    pub bytes_scanned: usize,
+}
+
+/// What [`analyze_with_stats`] would cost on a log, worked out without
+/// simulating any sprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AnalysisPlan {
+    pub competitor_count: usize,
+    /// `(s, t, winner)` candidates that would be simulated.
+    pub candidate_count: usize,
+    /// Upper bound on [`AnalysisStats::bytes_scanned`]: the input once, plus
+    /// `s + 1` positions for every candidate.
+    pub worst_case_bytes_scanned: usize,
+}
+
+/// A dry run of the analysis: counts the log's points and lists its
+/// candidates, but simulates nothing.
+pub fn analysis_plan(log: &str) -> AnalysisPlan {
+    let a = log.bytes().filter(|&b| b == b'A').count();
+    let b = log.bytes().filter(|&b| b == b'B').count();
+    let worst_case_bytes_scanned = log.len()
+        + [a, b]
+            .into_iter()
+            .flat_map(divisor_pairs)
+            .map(|(s, _)| s + 1)
+            .sum::<usize>();
+    AnalysisPlan {
+        competitor_count: competitor_count(log),
+        candidate_count: candidate_count(log),
+        worst_case_bytes_scanned,
+    }
+}
+
+/// [`analyze_sales_sprints`](crate::analyze_sales_sprints) with counters for
+/// the work it did.
+///
+/// The log is read once to build the point index, and each sprint afterwards
+/// costs a single lookup, so `bytes_scanned` stays near `n + candidates`
+/// rather than the `n * candidates` of rescanning per candidate.
+pub fn analyze_with_stats(log: &str) -> (Vec<Outcome>, AnalysisStats) {
+    let stream = stream_of(log);
+    let mut stats = AnalysisStats {
+        candidates: 0,
+        bytes_scanned: log.len(),
+    };
+    let mut outcomes = Vec::new();
+    for side in [Side::First, Side::Second] {
+        for (s, t) in divisor_pairs(stream.points(side)) {
+            let (valid, sprints) = stream.check_sweep(s, t, side);
+            stats.candidates += 1;
+            stats.bytes_scanned += sprints;
+            if valid {
+                outcomes.push(Outcome::new(s, t, winner_char(side, 'A', 'B')));
+            }
+        }
+    }
+    outcomes.sort();
+    (outcomes, stats)
+}
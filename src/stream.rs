@@ -0,0 +1,220 @@
+/// One of the two competitors in a log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    First,
+    Second,
+}
+
+/// A completed sprint, ending just before cleaned index `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Sprint {
+    pub(crate) end: usize,
+    pub(crate) winner: Side,
+}
+
+/// A cleaned log indexed by point position, so the end of any sprint can be
+/// found in constant time instead of rescanning the characters.
+pub(crate) struct Stream {
+    first: Vec<usize>,
+    second: Vec<usize>,
+    first_before: Vec<usize>,
+}
+
+impl Stream {
+    pub(crate) fn from_sides<I: IntoIterator<Item = Side>>(sides: I) -> Self {
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        let mut first_before = vec![0];
+        for (i, side) in sides.into_iter().enumerate() {
+            match side {
+                Side::First => first.push(i),
+                Side::Second => second.push(i),
+            }
+            first_before.push(first.len());
+        }
+        Stream {
+            first,
+            second,
+            first_before,
+        }
+    }
+
+    /// The first `len` points as a stream of their own, copied out of the
+    /// existing index rather than rebuilt from the sides.
+    pub(crate) fn prefix(&self, len: usize) -> Stream {
+        let first_len = self.first_before[len];
+        Stream {
+            first: self.first[..first_len].to_vec(),
+            second: self.second[..len - first_len].to_vec(),
+            first_before: self.first_before[..=len].to_vec(),
+        }
+    }
+
+    /// Drops the last point, returning who scored it.
+    pub(crate) fn pop(&mut self) -> Option<Side> {
+        let last = self.len().checked_sub(1)?;
+        self.first_before.pop();
+        if self.first.last() == Some(&last) {
+            self.first.pop();
+            Some(Side::First)
+        } else {
+            self.second.pop();
+            Some(Side::Second)
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.first_before.len() - 1
+    }
+
+    pub(crate) fn points(&self, side: Side) -> usize {
+        self.positions(side).len()
+    }
+
+    /// Cleaned indices of every point scored by `side`.
+    pub(crate) fn positions(&self, side: Side) -> &[usize] {
+        match side {
+            Side::First => &self.first,
+            Side::Second => &self.second,
+        }
+    }
+
+    /// Points scored by `side` in the cleaned range `start..end`.
+    pub(crate) fn points_in(&self, side: Side, start: usize, end: usize) -> usize {
+        let first = self.first_before[end] - self.first_before[start];
+        match side {
+            Side::First => first,
+            Side::Second => end - start - first,
+        }
+    }
+
+    /// The sprint of target `t` starting at cleaned index `start`, if one
+    /// completes before the stream runs out.
+    pub(crate) fn sprint_from(&self, start: usize, t: usize) -> Option<Sprint> {
+        let first_seen = self.first_before[start];
+        let second_seen = start - first_seen;
+        let first_end = self.first.get(first_seen + t - 1).map(|&i| i + 1);
+        let second_end = self.second.get(second_seen + t - 1).map(|&i| i + 1);
+        match (first_end, second_end) {
+            (Some(a), Some(b)) if b < a => Some(Sprint {
+                end: b,
+                winner: Side::Second,
+            }),
+            (Some(a), _) => Some(Sprint {
+                end: a,
+                winner: Side::First,
+            }),
+            (None, Some(b)) => Some(Sprint {
+                end: b,
+                winner: Side::Second,
+            }),
+            (None, None) => None,
+        }
+    }
+
+    /// Every complete sprint of target `t`, in order; a trailing partial
+    /// sprint is not reported.
+    pub(crate) fn sprints(&self, t: usize) -> Sprints<'_> {
+        Sprints {
+            stream: self,
+            t,
+            pos: 0,
+        }
+    }
+
+    /// Whether the whole stream is exactly `s` sprints of target `t`, each
+    /// won by `side`.
+    pub(crate) fn is_sweep(&self, s: usize, t: usize, side: Side) -> bool {
+        self.check_sweep(s, t, side).0
+    }
+
+    /// [`Stream::is_sweep`] along with how many sprints it looked at.
+    pub(crate) fn check_sweep(&self, s: usize, t: usize, side: Side) -> (bool, usize) {
+        let mut played = 0;
+        let mut end = 0;
+        for sprint in self.sprints(t) {
+            if sprint.winner != side || played == s {
+                return (false, played + 1);
+            }
+            played += 1;
+            end = sprint.end;
+        }
+        (played == s && end == self.len(), played)
+    }
+
+    /// The `(s, winner)` of the sweep at target `t`, if the whole stream is
+    /// sprints of target `t` all won by one side.
+    ///
+    /// Each sprint of a sweep closes on one of the winner's points, so the
+    /// side that scored the last point must have a multiple of `t` points;
+    /// any other `t` is rejected without walking a single sprint.
+    pub(crate) fn sweep_at(&self, t: usize) -> Option<(usize, Side)> {
+        let last = self.len().checked_sub(1)?;
+        let closer = if self.first.last() == Some(&last) {
+            Side::First
+        } else {
+            Side::Second
+        };
+        if t == 0 || !self.points(closer).is_multiple_of(t) {
+            return None;
+        }
+        let mut sprints = self.sprints(t);
+        let first = sprints.next()?;
+        let mut played = 1;
+        let mut end = first.end;
+        for sprint in sprints {
+            if sprint.winner != first.winner {
+                return None;
+            }
+            played += 1;
+            end = sprint.end;
+        }
+        (end == self.len()).then_some((played, first.winner))
+    }
+
+    /// Same answer as [`Stream::is_sweep`], found by walking back from the end.
+    ///
+    /// A sweep's sprints each close on one of the winner's points, every `t`
+    /// of them, so their boundaries can be read off from the last point
+    /// backwards; each sprint then only has to hold fewer than `t` points for
+    /// the loser.
+    pub(crate) fn is_sweep_backward(&self, s: usize, t: usize, side: Side) -> bool {
+        let positions = self.positions(side);
+        if s * t != positions.len() || positions.last() != self.len().checked_sub(1).as_ref() {
+            return false;
+        }
+        let loser = match side {
+            Side::First => Side::Second,
+            Side::Second => Side::First,
+        };
+        let mut end = self.len();
+        for k in 1..=s {
+            let start = if k == s {
+                0
+            } else {
+                positions[positions.len() - 1 - k * t] + 1
+            };
+            if self.points_in(loser, start, end) >= t {
+                return false;
+            }
+            end = start;
+        }
+        true
+    }
+}
+
+pub(crate) struct Sprints<'a> {
+    stream: &'a Stream,
+    t: usize,
+    pos: usize,
+}
+
+impl Iterator for Sprints<'_> {
+    type Item = Sprint;
+
+    fn next(&mut self) -> Option<Sprint> {
+        let sprint = self.stream.sprint_from(self.pos, self.t)?;
+        self.pos = sprint.end;
+        Some(sprint)
+    }
+}
@@ -0,0 +1,80 @@
+use crate::analysis::{stream_of, visit_sweep_sides, visit_sweeps};
+use crate::outcome::Outcome;
+use crate::stream::Side;
+
+/// Aggregate statistics over a set of outcomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Summary {
+    pub count: usize,
+    pub a_wins: usize,
+    pub b_wins: usize,
+    pub min_t: Option<usize>,
+    pub max_t: Option<usize>,
+}
+
+impl Summary {
+    fn add(&mut self, outcome: &Outcome) {
+        self.count += 1;
+        match outcome.winner {
+            'A' => self.a_wins += 1,
+            'B' => self.b_wins += 1,
+            _ => {}
+        }
+        self.min_t = Some(self.min_t.map_or(outcome.t, |t| t.min(outcome.t)));
+        self.max_t = Some(self.max_t.map_or(outcome.t, |t| t.max(outcome.t)));
+    }
+}
+
+/// Summarizes an already computed outcome list.
+pub fn summarize(outcomes: &[Outcome]) -> Summary {
+    let mut summary = Summary::default();
+    for outcome in outcomes {
+        summary.add(outcome);
+    }
+    summary
+}
+
+/// Same as `summarize(&analyze_sales_sprints(log))`, but folds each outcome
+/// in as it is validated instead of collecting the list first.
+pub fn summarize_streaming(log: &str) -> Summary {
+    let mut summary = Summary::default();
+    visit_sweeps(&stream_of(log), 'A', 'B', |o| summary.add(&o));
+    summary
+}
+
+/// The `(a_wins, b_wins)` of [`summarize_streaming`] alone, counted while
+/// validating without building any outcome.
+pub fn winner_counts(log: &str) -> (usize, usize) {
+    let (mut a_wins, mut b_wins) = (0, 0);
+    visit_sweep_sides(&stream_of(log), |_, _, side| match side {
+        Side::First => a_wins += 1,
+        Side::Second => b_wins += 1,
+    });
+    (a_wins, b_wins)
+}
+
+/// The number of outcomes with an even `t` and with an odd `t`, in that
+/// order, counted while validating without building any outcome.
+pub fn target_parity_counts(log: &str) -> (usize, usize) {
+    let (mut even, mut odd) = (0, 0);
+    visit_sweep_sides(&stream_of(log), |_, t, _| {
+        if t % 2 == 0 {
+            even += 1;
+        } else {
+            odd += 1;
+        }
+    });
+    (even, odd)
+}
+
+/// Shannon entropy, in bits, of the winner over the log's outcomes: 0 when
+/// one side wins them all (or there are none), 1 for an even split.
+pub fn winner_entropy(log: &str) -> f64 {
+    let (a_wins, b_wins) = winner_counts(log);
+    let total = (a_wins + b_wins) as f64;
+    [a_wins, b_wins]
+        .into_iter()
+        .filter(|&wins| wins > 0)
+        .map(|wins| wins as f64 / total * (total / wins as f64).log2())
+        .fold(0.0, |entropy, term| entropy + term)
+}
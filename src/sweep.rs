@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use crate::analysis::{clean_with_map, stream_of, stream_of_bytes, sweep_outcomes, winner_char};
+use crate::outcome::Outcome;
+use crate::stream::Stream;
+
+/// A log analyzed once so that outcomes can be queried target by target, e.g.
+/// from a UI slider, without re-cleaning or rescanning it.
+///
+/// Every divisor target is validated up front, which walks at most `n / t`
+/// sprints for each divisor `t` of a side's `n` points, `O(σ(n))` per side
+/// in all. Each query is then a single lookup.
+pub struct TargetSweep {
+    by_target: HashMap<usize, Outcome>,
+}
+
+impl TargetSweep {
+    pub fn new(log: &str) -> Self {
+        let outcomes = sweep_outcomes(&stream_of(log), 'A', 'B');
+        TargetSweep {
+            by_target: outcomes.into_iter().map(|o| (o.t, o)).collect(),
+        }
+    }
+
+    /// The outcome with target `t`, if the log has one.
+    pub fn outcome_at(&self, t: usize) -> Option<Outcome> {
+        self.by_target.get(&t).copied()
+    }
+}
+
+/// A cleaned log that can be cut back one point at a time, e.g. for undo in
+/// an editor, without cleaning the text again.
+///
+/// Ignored characters are dropped up front, so [`TruncatableAnalyzer::pop`]
+/// always removes the last `'A'` or `'B'`. Each call to
+/// [`TruncatableAnalyzer::outcomes`] re-enumerates the candidates, since the
+/// divisors change with the length.
+pub struct TruncatableAnalyzer {
+    stream: Stream,
+}
+
+impl TruncatableAnalyzer {
+    pub fn new(log: &str) -> Self {
+        TruncatableAnalyzer {
+            stream: stream_of(log),
+        }
+    }
+
+    /// Removes the last competitor character, returning it, or `None` once
+    /// the log is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        self.stream.pop().map(|side| winner_char(side, 'A', 'B'))
+    }
+
+    /// The outcomes of what is left of the log.
+    pub fn outcomes(&self) -> Vec<Outcome> {
+        sweep_outcomes(&self.stream, 'A', 'B')
+    }
+}
+
+/// A log cleaned and indexed once for answering many prefix queries, e.g.
+/// to update a progress bar as more of the log is read.
+///
+/// Each prefix is cut out of the shared point index, and results are cached
+/// by how many competitor characters the prefix holds, so prefixes that only
+/// differ in ignored characters are analyzed once.
+pub struct PrefixAnalyzer {
+    stream: Stream,
+    offsets: Vec<usize>,
+    cache: HashMap<usize, Vec<Outcome>>,
+}
+
+impl PrefixAnalyzer {
+    pub fn new(log: &str) -> Self {
+        let (cleaned, offsets) = clean_with_map(log);
+        PrefixAnalyzer {
+            stream: stream_of_bytes(&cleaned),
+            offsets,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// The outcomes of the log's first `k` bytes; `k` past the end means the
+    /// whole log.
+    pub fn outcomes_for_prefix(&mut self, k: usize) -> Vec<Outcome> {
+        let len = self.offsets.partition_point(|&offset| offset < k);
+        let stream = &self.stream;
+        self.cache
+            .entry(len)
+            .or_insert_with(|| sweep_outcomes(&stream.prefix(len), 'A', 'B'))
+            .clone()
+    }
+}
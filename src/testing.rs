@@ -0,0 +1,31 @@
+//! Invariant checks for downstream tests, enabled by the `testing` feature.
+
+use crate::analysis::stream_of;
+use crate::outcome::Outcome;
+
+/// Checks that every outcome plays exactly `s` sprints of target `t` over the
+/// log, the last of them closing on the log's final competitor character, so
+/// that the match neither ends early nor leaves a partial sprint behind.
+///
+/// # Panics
+///
+/// With a message naming the first outcome that breaks the invariant.
+pub fn assert_no_early_termination(log: &str, outcomes: &[Outcome]) {
+    let stream = stream_of(log);
+    for o in outcomes {
+        assert!(o.t > 0, "outcome {:?} should have a positive target", o);
+        let mut played = 0;
+        for sprint in stream.sprints(o.t) {
+            played += 1;
+            if played == o.s {
+                assert_eq!(
+                    sprint.end,
+                    stream.len(),
+                    "outcome {:?} should end at the end of the log",
+                    o
+                );
+            }
+        }
+        assert_eq!(played, o.s, "outcome {:?} should have exactly s sprints", o);
+    }
+}
@@ -1,5 +1,31 @@
-use sales_sprint_log_analyzer::analyze_sales_sprints;
+use sales_sprint_log_analyzer::{
+    all_outcomes_for_length, analysis_plan, analyze_allow_early, analyze_chars, analyze_circular,
+    analyze_classified, analyze_detailed_iter, analyze_dir, analyze_dual_pairs, analyze_file,
+    analyze_fixed_win_target, analyze_flat, analyze_max_sprints, analyze_min_length, analyze_multi,
+    analyze_outcomes, analyze_reach_or_exceed, analyze_reset_on_noise, analyze_reverse,
+    analyze_rle, analyze_sales_sprints, analyze_sales_sprints_bytes,
+    analyze_sales_sprints_detailed, analyze_sales_sprints_strict,
+    analyze_sales_sprints_with_capacity, analyze_sliding, analyze_sorted_by_winner,
+    analyze_step_points, analyze_target_range, analyze_timed, analyze_to_channel, analyze_tokens,
+    analyze_utf16, analyze_weighted_input, analyze_with_header, analyze_with_ignored_count,
+    analyze_with_predicate, analyze_with_progress, analyze_with_ranges, analyze_with_stats,
+    analyze_with_table, analyze_with_tie_policy, assert_length_invariant, balance_point,
+    boundaries_agree, candidate_count, canonical_log, check_decomposition, clean_log,
+    clean_with_map, common_structures, competitor_count, decisiveness, dedup_batch,
+    dedup_batch_with, dedup_outcomes, dedup_outcomes_iter, deserialize_binary,
+    detailed_outcomes_to_tsv, divisor_count, divisor_pairs, extreme_outcomes,
+    first_invalid_context, has_swap_symmetry, is_ambiguous, is_balanced, is_prime_length,
+    is_single_sprint_match, longest_run, margin_histogram, median_outcome, modal_target,
+    most_dominant, outcomes_by_target, outcomes_won_by, report, running_winners, serialize_binary,
+    sprint_sequence_hash, sprint_winner_string, structure_similarity, summarize,
+    summarize_streaming, target_parity_counts, to_dot, upsets_against, weighted_winner_totals,
+    winner_counts, winner_entropy, winners_per_sprint_count, AnalysisConfig, AnalysisResult,
+    CharClass, DetailedOutcome, MatchCategory, Outcome, PrefixAnalyzer, Rule, SprintError,
+    TargetSweep, TiePolicy, TruncatableAnalyzer,
+};
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
 
 fn simplify(log: &str) -> Vec<(usize, usize, char)> {
     analyze_sales_sprints(log)
@@ -8,10 +34,15 @@ fn simplify(log: &str) -> Vec<(usize, usize, char)> {
         .collect()
 }
 
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sales_sprint_log_analyzer-{}-{}", std::process::id(), name))
+}
+
 fn compute_single_char_expected(c: char) -> Vec<(usize, usize, char)> {
     vec![(1, 1, c)]
 }
 
+#[allow(clippy::manual_is_multiple_of)]
 fn compute_identical_chars_expected(c: char, count: usize) -> Vec<(usize, usize, char)> {
     let mut result = Vec::new();
     for s in 1..=count {
@@ -28,6 +59,7 @@ fn compute_prime_length_expected(c: char, count: usize) -> Vec<(usize, usize, ch
     vec![(1, count, c), (count, 1, c)]
 }
 
+#[allow(clippy::manual_is_multiple_of)]
 fn compute_large_identical_expected(c: char, count: usize) -> Vec<(usize, usize, char)> {
     let mut result = Vec::new();
     for s in 1..=count {
@@ -319,3 +351,1283 @@ fn test_case_sensitivity() {
     verify_no_early_termination("AaBb", &result);
 }
 
+#[test]
+fn test_candidate_count_matches_divisor_pairs() {
+    let log = "A".repeat(12);
+    assert_eq!(candidate_count(&log), 6, "12 'A's should have exactly 6 candidates");
+    assert_eq!(candidate_count(&log), divisor_pairs(competitor_count(&log)).len(), "Candidate count should match the divisor pair enumeration");
+}
+
+#[test]
+fn test_summarize_streaming_matches_summarize() {
+    let long_string = "A".repeat(1000);
+    for log in ["", "A", "AB", "ABA", "AABBB", "AXB", "BBBBBB", "AAAAAAAAAAAA", long_string.as_str()] {
+        assert_eq!(summarize_streaming(log), summarize(&analyze_sales_sprints(log)), "Streaming summary should match for {:?}", log);
+    }
+}
+
+#[test]
+fn test_analyze_reverse_matches_forward() {
+    let long_string = "A".repeat(100);
+    for log in ["", "A", "AB", "ABA", "AABBAA", "ABABA", "AABBB", "BAA", "AAAB", "A!B@A", "BABBAB", "AAAAAAAAAAAA", long_string.as_str()] {
+        assert_eq!(analyze_reverse(log), analyze_sales_sprints(log), "Reverse simulation should agree for {:?}", log);
+    }
+}
+
+#[test]
+fn test_all_outcomes_for_length_matches_identical_log() {
+    for n in [0, 1, 2, 5, 12, 36, 97] {
+        assert_eq!(all_outcomes_for_length(n, 'A'), analyze_sales_sprints(&"A".repeat(n)), "Hypothetical outcomes should match an all-'A' log of length {}", n);
+    }
+}
+
+#[test]
+fn test_wide_and_scalar_cleaning_agree() {
+    let pattern = "AB!\u{00E1}BBxA\u{4E2D}AAB@";
+    for len in 0..40 {
+        let log: String = pattern.chars().cycle().take(len).collect();
+        let scalar: Vec<u8> = log.bytes().filter(|&b| b == b'A' || b == b'B').collect();
+        assert_eq!(clean_log(&log), scalar, "Cleaned stream should match the scalar filter for length {}", len);
+        assert_eq!(competitor_count(&log), scalar.len(), "Competitor count should match the scalar filter for length {}", len);
+    }
+    let all_competitors = "AB".repeat(33);
+    assert_eq!(competitor_count(&all_competitors), 66, "Full lanes of competitors should all be counted");
+}
+
+#[test]
+fn test_is_prime_length() {
+    assert!(is_prime_length("AAAAA"), "Length-5 log should be prime-structured");
+    assert!(!is_prime_length("AAAA"), "Length-4 log should not be prime-structured");
+    assert!(is_prime_length("A!A@B#AB"), "Only competitor characters should count towards the length");
+    assert!(!is_prime_length("A"), "Length 1 is not prime");
+}
+
+#[test]
+fn test_dedup_outcomes_merges_duplicates_and_rejects_conflicts() {
+    let merged = dedup_outcomes(vec![Outcome::new(2, 2, 'A'), Outcome::new(1, 4, 'A'), Outcome::new(2, 2, 'A')]);
+    assert_eq!(merged, Ok(vec![Outcome::new(1, 4, 'A'), Outcome::new(2, 2, 'A')]), "Exact duplicates should be merged and sorted");
+
+    let conflicting = dedup_outcomes(vec![Outcome::new(2, 2, 'B'), Outcome::new(1, 4, 'A'), Outcome::new(2, 2, 'A')]);
+    assert_eq!(
+        conflicting,
+        Err(SprintError::ConflictingWinners { s: 2, t: 2, winners: ('A', 'B') }),
+        "Differing winners for the same (s, t) should be reported"
+    );
+}
+
+#[test]
+fn test_analyze_tokens_matches_char_log() {
+    let tokens = ["Team1", "Team1", "noise", "Team2", "Team2", "Team2"];
+    let result: Vec<(usize, usize, String)> = analyze_tokens(&tokens, "Team1", "Team2")
+        .into_iter()
+        .map(|o| (o.s, o.t, o.winner))
+        .collect();
+    let expected: Vec<(usize, usize, String)> = simplify("AA!BBB")
+        .into_iter()
+        .map(|(s, t, w)| (s, t, if w == 'A' { "Team1" } else { "Team2" }.to_string()))
+        .collect();
+    assert_eq!(result, expected, "Token analysis should mirror the corresponding A/B log");
+    assert_eq!(result, vec![(1, 3, "Team2".to_string())], "Team2 should win the single 3-point sprint");
+}
+
+#[test]
+fn test_default_config_matches_baseline() {
+    let long_string = "A".repeat(100);
+    let corpus = [
+        "", "A", "AA", "AB", "ABA", "AAAA", "AABBAA", "ABABAB", "AAAAA", "AAAAAAAAAAAA", "AABBB", "AAAB", "ABABA", "B",
+        "BBBBBB", "AABB", "AXB", "A!B@C#D", "A\u{00E1}B\u{00E9}", "A\u{00E1}A\u{00E9}B\u{00F1}B\u{00F3}B", "AaBb",
+        long_string.as_str(),
+    ];
+    let config = AnalysisConfig::default();
+    for log in corpus {
+        assert_eq!(config.analyze(log), analyze_sales_sprints(log), "Default config should reproduce the baseline for {:?}", log);
+    }
+}
+
+#[test]
+fn test_majority_rule_allows_split_sprints() {
+    let config = AnalysisConfig { rule: Rule::Majority, ..AnalysisConfig::default() };
+    assert_eq!(
+        config.analyze("AABBB"),
+        vec![Outcome::new(1, 3, 'B'), Outcome::new(5, 1, 'B')],
+        "Majority rule should accept B winning 3 of 5 single-point sprints"
+    );
+}
+
+#[test]
+fn test_margin_histogram_sums_to_outcome_count() {
+    for log in ["AAAA", "AAAAAAAAAAAA", "AABBB", "ABABAB", ""] {
+        let histogram = margin_histogram(log);
+        let total: usize = histogram.values().sum();
+        assert_eq!(total, analyze_sales_sprints(log).len(), "Histogram should account for every outcome of {:?}", log);
+    }
+    let histogram = margin_histogram("AAAA");
+    let expected: Vec<(usize, usize)> = vec![(1, 1), (2, 1), (4, 1)];
+    assert_eq!(histogram.into_iter().collect::<Vec<_>>(), expected, "All-'A' margins should equal each outcome's s");
+}
+
+#[test]
+fn test_strict_reports_byte_offsets() {
+    assert_eq!(analyze_sales_sprints_strict("AABBB"), Ok(analyze_sales_sprints("AABBB")), "Clean logs should analyze normally");
+
+    let log = "AB\u{00E9}X";
+    assert_eq!(
+        analyze_sales_sprints_strict(log),
+        Err(SprintError::InvalidCharacter { index: 2, ch: '\u{00E9}' }),
+        "First invalid character should be reported"
+    );
+
+    let config = AnalysisConfig { first: '\u{00E9}', second: '\u{00FC}', ..AnalysisConfig::default() };
+    let log = "\u{00E9}\u{00FC}X\u{00E9}";
+    match config.analyze_strict(log) {
+        Err(SprintError::InvalidCharacter { index, ch }) => {
+            assert_eq!((index, ch), (4, 'X'), "Offset should count bytes, not chars");
+            assert!(log[index..].starts_with('X'), "Offset should slice the log at the invalid character");
+        }
+        other => panic!("Expected an invalid character error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_outcomes_wrapper_helpers() {
+    let outcomes = analyze_outcomes("AAAA");
+    assert_eq!(outcomes.len(), 3, "Wrapper should report the outcome count");
+    assert!(!outcomes.is_empty(), "Wrapper should not be empty for 'AAAA'");
+    assert_eq!(outcomes.winners(), vec!['A', 'A', 'A'], "Winners should be listed in order");
+    assert_eq!(outcomes.by_winner('A').len(), 3, "All outcomes should be won by 'A'");
+    assert!(outcomes.by_winner('B').is_empty(), "No outcome should be won by 'B'");
+    assert_eq!(outcomes[1], Outcome::new(2, 2, 'A'), "Wrapper should index like a slice");
+    assert_eq!(outcomes.clone().into_iter().collect::<Vec<_>>(), analyze_sales_sprints("AAAA"), "Wrapper should iterate like the plain vector");
+    assert!(analyze_outcomes("AB").is_empty(), "No outcomes should give an empty wrapper");
+}
+
+#[test]
+fn test_analyze_max_sprints_caps_s() {
+    let result = analyze_max_sprints(&"A".repeat(12), 3);
+    assert_eq!(result, vec![Outcome::new(1, 12, 'A'), Outcome::new(2, 6, 'A'), Outcome::new(3, 4, 'A')], "Only s <= 3 outcomes should remain");
+    assert!(result.iter().all(|o| o.s <= 3), "Larger-s outcomes should be absent");
+    assert!(analyze_max_sprints("AAAA", 0).is_empty(), "A zero cap should allow nothing");
+}
+
+#[test]
+fn test_outcome_order_breaks_ties_by_winner() {
+    let mut outcomes = vec![Outcome::new(2, 2, 'B'), Outcome::new(1, 4, 'B'), Outcome::new(2, 2, 'A'), Outcome::new(1, 4, 'A')];
+    outcomes.sort();
+    assert_eq!(
+        outcomes,
+        vec![Outcome::new(1, 4, 'A'), Outcome::new(1, 4, 'B'), Outcome::new(2, 2, 'A'), Outcome::new(2, 2, 'B')],
+        "'A' should sort before 'B' when s and t tie"
+    );
+}
+
+#[test]
+fn test_target_sweep_matches_batch() {
+    let log = "A".repeat(12);
+    let sweep = TargetSweep::new(&log);
+    let swept: Vec<_> = (0..=13).filter_map(|t| sweep.outcome_at(t)).collect();
+    let mut batch = analyze_sales_sprints(&log);
+    batch.sort_by_key(|o| o.t);
+    assert_eq!(swept, batch, "Sweeping every target should reproduce the batch result");
+    for (s, t) in divisor_pairs(12) {
+        assert_eq!(sweep.outcome_at(t), Some(Outcome::new(s, t, 'A')), "Divisor target {} should have an outcome", t);
+    }
+    assert_eq!(TargetSweep::new("AABBB").outcome_at(1), None, "Split sprints should not produce an outcome");
+}
+
+#[test]
+fn test_target_sweep_rejects_non_divisor_targets() {
+    let sweep = TargetSweep::new(&"A".repeat(1_000_000));
+    assert_eq!(sweep.outcome_at(7), None, "7 does not divide a million points");
+    assert_eq!(sweep.outcome_at(8), Some(Outcome::new(125_000, 8, 'A')), "8 divides a million points");
+    assert_eq!(TargetSweep::new("ABBB").outcome_at(2), None, "B closes the log with 3 points, which 2 does not divide");
+    assert_eq!(TargetSweep::new("").outcome_at(1), None, "An empty log has no outcome");
+}
+
+#[test]
+fn test_is_ambiguous() {
+    assert!(!is_ambiguous("AAAA"), "Single-winner log should not be ambiguous");
+    assert!(!is_ambiguous("BAABBBBABAAA"), "Default rules always crown the owner of the last character");
+
+    let majority = AnalysisConfig { rule: Rule::Majority, ..AnalysisConfig::default() };
+    let log = "BAABBBBABAAA";
+    assert_eq!(
+        majority.analyze(log),
+        vec![Outcome::new(3, 3, 'B'), Outcome::new(5, 2, 'A')],
+        "Majority rule should give B at t=3 and A at t=2"
+    );
+    assert!(majority.is_ambiguous(log), "Log with both A- and B-won outcomes should be ambiguous");
+    assert_eq!(majority.possible_winners(log).len(), 2, "Both players should be possible winners");
+}
+
+#[test]
+fn test_analyze_with_ignored_count() {
+    let (outcomes, ignored) = analyze_with_ignored_count("A!B@A");
+    assert_eq!(outcomes, analyze_sales_sprints("A!B@A"), "Outcomes should match the plain analysis");
+    assert_eq!(outcomes, vec![Outcome::new(1, 2, 'A')], "'A!B@A' should read as 'ABA'");
+    assert_eq!(ignored, 2, "Two junk characters should be counted");
+    assert_eq!(analyze_with_ignored_count("A\u{00E1}B").1, 1, "Multi-byte characters should count once");
+}
+
+#[test]
+fn test_analyze_fixed_win_target() {
+    assert_eq!(analyze_fixed_win_target("AABBB", 3), vec![Outcome::new(3, 1, 'B')], "B should take 3 single-point sprints to A's 2");
+    assert_eq!(analyze_fixed_win_target("AABBAA", 2), vec![Outcome::new(2, 2, 'A')], "A should win sprints 1 and 3 of target 2");
+    assert!(analyze_fixed_win_target("AAAA", 0).is_empty(), "A zero win target should yield nothing");
+    for log in ["AAAA", "AABBB", "AAAAAAAAAAAA", "ABA"] {
+        for outcome in analyze_sales_sprints(log) {
+            assert!(
+                analyze_fixed_win_target(log, outcome.s).contains(&outcome),
+                "Default outcome {:?} of {:?} should be a fixed-win-target outcome",
+                outcome,
+                log
+            );
+        }
+    }
+}
+
+#[test]
+fn test_binary_round_trip_and_truncation() {
+    let outcomes = analyze_sales_sprints(&"A".repeat(12));
+    let bytes = serialize_binary(&outcomes).unwrap();
+    assert_eq!(bytes.len(), 4 + 9 * outcomes.len(), "Each outcome should take 9 bytes after the header");
+    assert_eq!(deserialize_binary(&bytes), Ok(outcomes), "Binary encoding should round-trip");
+
+    let truncated = &bytes[..bytes.len() - 1];
+    assert_eq!(
+        deserialize_binary(truncated),
+        Err(SprintError::BinaryLength { expected: bytes.len(), actual: bytes.len() - 1 }),
+        "Truncated input should be rejected"
+    );
+    assert!(deserialize_binary(&bytes[..2]).is_err(), "Input shorter than the header should be rejected");
+    assert_eq!(deserialize_binary(&serialize_binary(&[]).unwrap()), Ok(vec![]), "Empty lists should round-trip");
+}
+
+#[test]
+fn test_serialize_binary_rejects_non_ascii_winner() {
+    let outcomes = [Outcome::new(1, 2, 'A'), Outcome::new(2, 1, 'é')];
+    assert_eq!(serialize_binary(&outcomes), Err(SprintError::NonAsciiWinner { index: 1, winner: 'é' }), "A non-ASCII winner should be reported with its position");
+    assert!(serialize_binary(&outcomes[..1]).is_ok(), "ASCII winners should still encode");
+}
+
+#[test]
+fn test_analyze_multi_splits_matches() {
+    let result = analyze_multi("AAAA;AABBB", ';');
+    assert_eq!(result, vec![analyze_sales_sprints("AAAA"), vec![Outcome::new(1, 3, 'B')]], "Each match should be analyzed independently");
+    assert_eq!(analyze_multi("A;;B", ';'), vec![vec![Outcome::new(1, 1, 'A')], vec![], vec![Outcome::new(1, 1, 'B')]], "Empty segments should yield empty lists");
+}
+
+#[test]
+fn test_is_single_sprint_match() {
+    assert_eq!(is_single_sprint_match("AAAA"), Some('A'), "'AAAA' should be one sprint won by A");
+    assert_eq!(is_single_sprint_match("AABB"), None, "'AABB' should not be a single sprint");
+    assert_eq!(is_single_sprint_match("B!B"), Some('B'), "Ignored characters should not matter");
+    assert_eq!(is_single_sprint_match(""), None, "Empty log has no sprint");
+}
+
+#[test]
+fn test_analyze_with_stats_matches_plain_analysis() {
+    let log = "A".repeat(60);
+    let (outcomes, stats) = analyze_with_stats(&log);
+    assert_eq!(outcomes, analyze_sales_sprints(&log), "Instrumented analysis should give the same outcomes");
+    assert_eq!(stats.candidates, candidate_count(&log), "Every candidate should be counted");
+}
+
+#[cfg(feature = "reference")]
+#[test]
+fn test_indexed_scan_beats_reference() {
+    use sales_sprint_log_analyzer::reference;
+
+    let log = "AAB".repeat(40) + &"A".repeat(120);
+    let (outcomes, stats) = analyze_with_stats(&log);
+    let (reference_outcomes, reference_stats) = reference::analyze_with_stats(&log);
+    assert_eq!(outcomes, reference_outcomes, "Both implementations should agree");
+    assert!(
+        stats.bytes_scanned < reference_stats.bytes_scanned,
+        "Indexed scan ({}) should touch fewer bytes than the reference ({})",
+        stats.bytes_scanned,
+        reference_stats.bytes_scanned
+    );
+}
+
+#[test]
+fn test_weighted_winner_totals() {
+    // "ABAABA" reads as (1,4,'A') and (2,2,'A'): A wins 1 + 2 sprints in total.
+    assert_eq!(simplify("ABAABA"), vec![(1, 4, 'A'), (2, 2, 'A')], "Fixture should have two A-won outcomes");
+    assert_eq!(weighted_winner_totals("ABAABA"), (3, 0), "A's sprint wins should be summed across outcomes");
+    assert_eq!(weighted_winner_totals("BBBB"), (0, 7), "All-'B' length 4 should sum 1 + 2 + 4 sprints for B");
+}
+
+#[test]
+fn test_tie_policies_on_even_split() {
+    assert_eq!(analyze_with_tie_policy("AABB", TiePolicy::Exclude), analyze_sales_sprints("AABB"), "Exclude should match the default analysis");
+    assert!(analyze_with_tie_policy("AABB", TiePolicy::Exclude).is_empty(), "Tied readings should be excluded");
+    assert_eq!(
+        analyze_with_tie_policy("AABB", TiePolicy::LastSprintWins),
+        vec![Outcome::new(2, 2, 'B'), Outcome::new(4, 1, 'B')],
+        "Last sprint winner should take tied readings"
+    );
+    assert_eq!(
+        analyze_with_tie_policy("AABB", TiePolicy::FirstSprintWins),
+        vec![Outcome::new(2, 2, 'A'), Outcome::new(4, 1, 'A')],
+        "First sprint winner should take tied readings"
+    );
+    assert!(analyze_with_tie_policy("AABBA", TiePolicy::LastSprintWins).iter().all(|o| o.t != 2), "Incomplete trailing sprints stay invalid");
+    for log in ["AAAA", "AABBB", "ABAABA", ""] {
+        assert_eq!(analyze_with_tie_policy(log, TiePolicy::Exclude), analyze_sales_sprints(log), "Exclude should match the default for {:?}", log);
+    }
+}
+
+#[test]
+fn test_bytes_api_matches_str_and_tolerates_invalid_utf8() {
+    for log in ["", "AABBB", "A!B@A", "AAAAAAAAAAAA", "A\u{00E1}A\u{00E9}B\u{00F1}B\u{00F3}B"] {
+        assert_eq!(analyze_sales_sprints_bytes(log.as_bytes()), analyze_sales_sprints(log), "Byte analysis should match for {:?}", log);
+    }
+    assert_eq!(analyze_sales_sprints_bytes(&[0xff, b'A', 0xc3, b'A']), vec![Outcome::new(1, 2, 'A'), Outcome::new(2, 1, 'A')], "Invalid UTF-8 bytes should be ignored");
+}
+
+#[test]
+fn test_divisor_count_predicts_identical_log_outcomes() {
+    for n in [1, 2, 12, 60, 360, 720, 840] {
+        assert_eq!(analyze_sales_sprints(&"B".repeat(n)).len(), divisor_count(n), "All-identical log of length {} should have divisor_count outcomes", n);
+        assert_eq!(divisor_count(n), compute_identical_chars_expected('B', n).len(), "Factorization should agree with brute force for {}", n);
+    }
+    assert_eq!(divisor_count(0), 0, "Zero has no divisor pairs");
+}
+
+#[test]
+fn test_analyze_rle_matches_expanded_log() {
+    assert_eq!(analyze_rle(&[('A', 4)]).unwrap(), analyze_sales_sprints("AAAA"), "A run of four 'A's should match 'AAAA'");
+    let runs = [('A', 1), ('B', 1), ('A', 2), ('!', 3), ('B', 1), ('A', 0), ('A', 1)];
+    let expanded: String = runs.iter().map(|&(c, n)| c.to_string().repeat(n)).collect();
+    assert_eq!(analyze_rle(&runs).unwrap(), analyze_sales_sprints(&expanded), "Mixed runs should match the expanded log");
+    assert_eq!(analyze_rle(&[('B', 2), ('A', 3)]).unwrap(), vec![Outcome::new(1, 3, 'A')], "BBAAA should be one sprint won by A");
+    assert_eq!(analyze_rle(&[('A', 1_000_000)]).unwrap().len(), divisor_count(1_000_000), "A million-'A' run should have divisor_count outcomes");
+}
+
+#[test]
+fn test_analyze_with_table_custom_classes() {
+    let mut table = [CharClass::Ignore; 256];
+    table[b'0' as usize] = CharClass::First;
+    table[b'1' as usize] = CharClass::Second;
+    assert_eq!(analyze_with_table(b"00x111", &table), analyze_sales_sprints("AABBB"), "'0'/'1' should play the roles of 'A'/'B'");
+    assert_eq!(analyze_with_table(b"0000", &table), analyze_sales_sprints("AAAA"), "All-'0' input should match all-'A'");
+    assert!(analyze_with_table(b"AAAA", &table).is_empty(), "Bytes mapped to Ignore should not count");
+}
+
+#[test]
+fn test_analyze_file_reads_log() {
+    let path = temp_path("analyze_file.log");
+    std::fs::write(&path, "AA!BBB\n").unwrap();
+    let result = analyze_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(result.unwrap(), analyze_sales_sprints("AABBB"), "File analysis should match the in-memory log");
+    assert!(analyze_file(temp_path("missing.log")).is_err(), "Missing files should surface an io error");
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_analyze_file_async_matches_sync() {
+    let path = temp_path("analyze_file_async.log");
+    std::fs::write(&path, "A".repeat(12)).unwrap();
+    let async_result = sales_sprint_log_analyzer::analyze_file_async(&path).await.unwrap();
+    let sync_result = analyze_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(async_result, sync_result, "Async analysis should match the sync path");
+}
+
+#[test]
+fn test_structurally_eq_ignores_winner() {
+    let a = Outcome::new(2, 2, 'A');
+    let b = Outcome::new(2, 2, 'B');
+    assert!(a.structurally_eq(&b), "Same (s, t) should be structurally equal");
+    assert_ne!(a, b, "Different winners should not be equal");
+    assert!(!a.structurally_eq(&Outcome::new(1, 4, 'A')), "Different (s, t) should not be structurally equal");
+}
+
+#[test]
+fn test_extreme_outcomes() {
+    assert_eq!(
+        extreme_outcomes("AAAAA"),
+        (Some(Outcome::new(5, 1, 'A')), Some(Outcome::new(1, 5, 'A'))),
+        "All-'A' log should have both extremes"
+    );
+    assert_eq!(extreme_outcomes("AABBB"), (None, Some(Outcome::new(1, 3, 'B'))), "Mixed log should only have the single-sprint reading");
+    assert_eq!(extreme_outcomes("AABB"), (None, None), "Impossible split should have neither extreme");
+}
+
+#[test]
+fn test_report_writes_cleaned_log_and_outcomes() {
+    let mut buf = Vec::new();
+    report("A!B@A", &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), "ABA\n1 2 A\n", "Report should list the cleaned log then each outcome");
+    let mut buf = Vec::new();
+    report("AAAA", &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), "AAAA\n1 4 A\n2 2 A\n4 1 A\n", "Every outcome should get its own line");
+}
+
+#[test]
+fn test_swap_symmetry() {
+    assert!(has_swap_symmetry("AAAA"), "All-identical logs should be symmetric under (s, t) swap");
+    assert!(has_swap_symmetry("BBBBBB"), "All-'B' logs should be symmetric too");
+    assert!(!has_swap_symmetry("AABBB"), "(1, 3, 'B') has no (3, 1, 'B') counterpart");
+}
+
+#[test]
+fn test_analyze_chars_from_filtered_iterator() {
+    let chars = "A!B@A".chars().filter(|c| c.is_alphabetic());
+    assert_eq!(analyze_chars(chars, 'A', 'B'), analyze_sales_sprints("A!B@A"), "Filtered chars should match the str analysis");
+    assert_eq!(
+        analyze_chars("xxyyy".chars(), 'x', 'y'),
+        vec![Outcome::new(1, 3, 'y')],
+        "Outcomes should be won by the named competitors"
+    );
+}
+
+#[test]
+fn test_canonical_log_reconstructs_identical_log() {
+    assert_eq!(canonical_log(&analyze_sales_sprints("AAAA")), Some("AAAA".to_string()), "AAAA should be rebuilt from its outcomes");
+    let mut shuffled = analyze_sales_sprints("BBBBBB");
+    shuffled.reverse();
+    assert_eq!(canonical_log(&shuffled), Some("BBBBBB".to_string()), "Outcome order should not matter");
+    assert_eq!(canonical_log(&analyze_sales_sprints("AABBB")), None, "A mixed log's outcomes are not a full divisor set");
+    assert_eq!(canonical_log(&[Outcome::new(1, 4, 'A'), Outcome::new(4, 1, 'A')]), None, "Missing (2, 2) should not be canonical");
+    assert_eq!(canonical_log(&[]), None, "No outcomes should give no log");
+}
+
+#[test]
+fn test_analyze_dir_sorted_by_path() {
+    let dir = temp_path("analyze_dir");
+    std::fs::create_dir_all(dir.join("nested")).unwrap();
+    std::fs::write(dir.join("b.log"), "AABBB").unwrap();
+    std::fs::write(dir.join("a.log"), "AAAA").unwrap();
+    let result = analyze_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        result.unwrap(),
+        vec![(dir.join("a.log"), analyze_sales_sprints("AAAA")), (dir.join("b.log"), analyze_sales_sprints("AABBB"))],
+        "Files should be analyzed in path order and subdirectories skipped"
+    );
+    assert!(analyze_dir(temp_path("missing_dir")).is_err(), "A missing directory should surface an io error");
+}
+
+#[test]
+fn test_decided_in_last_sprint() {
+    assert!(analyze_fixed_win_target("ABA", 2).contains(&Outcome::new(2, 1, 'A')), "ABA should read as A first to 2 sprint wins");
+    assert!(Outcome::new(2, 1, 'A').decided_in_last_sprint("ABA"), "A, B, A should be level at 1-1 going into the last sprint");
+    assert!(Outcome::new(3, 1, 'B').decided_in_last_sprint("BAABB"), "B should take the decider from 2-2");
+    assert!(!Outcome::new(2, 1, 'A').decided_in_last_sprint("AA"), "A sweep is decided before the last sprint");
+    assert!(!Outcome::new(1, 3, 'A').decided_in_last_sprint("AAA"), "A single sprint has nothing to be level at");
+    assert!(!Outcome::new(1, 1, 'A').decided_in_last_sprint("A"), "s == 1 should never count as a decider");
+    assert!(!Outcome::new(3, 1, 'A').decided_in_last_sprint("ABA"), "A never reaches 3 wins in ABA");
+    assert!(!Outcome::new(2, 1, 'A').decided_in_last_sprint("ABAB"), "Points after the deciding sprint should disqualify the reading");
+    assert!(!Outcome::new(2, 1, 'B').decided_in_last_sprint("ABA"), "The wrong winner should not qualify");
+}
+
+#[test]
+fn test_analyze_target_range_is_inclusive() {
+    let log = "A".repeat(12);
+    assert_eq!(
+        analyze_target_range(&log, 2, 4),
+        vec![Outcome::new(3, 4, 'A'), Outcome::new(4, 3, 'A'), Outcome::new(6, 2, 'A')],
+        "Both bounds should be included"
+    );
+    assert_eq!(analyze_target_range(&log, 5, 5), Vec::new(), "5 does not divide 12");
+    assert_eq!(analyze_target_range(&log, 1, 12), analyze_sales_sprints(&log), "The full range should match the default analysis");
+    assert!(analyze_target_range(&log, 4, 2).is_empty(), "An inverted range should be empty");
+}
+
+#[test]
+fn test_outcomes_consume_s_times_t_points() {
+    for log in ["AAAA", "BBBBBB", "A!A@A", &"A".repeat(12)] {
+        assert_length_invariant(log);
+        for o in analyze_sales_sprints(log) {
+            assert_eq!(o.s * o.t, competitor_count(log), "{:?} should consume every point of single-competitor {:?}", o, log);
+        }
+    }
+    for log in ["AABBB", "ABAABA", "BBAAA", "A!B@A"] {
+        assert_length_invariant(log);
+        for o in analyze_sales_sprints(log) {
+            let points = log.chars().filter(|&c| c == o.winner).count();
+            assert_eq!(o.s * o.t, points, "{:?} should consume every point of its winner in {:?}", o, log);
+        }
+    }
+}
+
+#[test]
+fn test_analyze_flat_decodes_to_outcomes() {
+    for log in ["", "AAAA", "AABBB", &"B".repeat(12)] {
+        let flat = analyze_flat(log);
+        assert_eq!(flat.len() % 3, 0, "Flat output should be whole triples");
+        let decoded: Vec<Outcome> = flat
+            .chunks_exact(3)
+            .map(|c| Outcome::new(c[0] as usize, c[1] as usize, char::from_u32(c[2]).unwrap()))
+            .collect();
+        assert_eq!(decoded, analyze_sales_sprints(log), "Decoded triples should match for {:?}", log);
+    }
+    assert_eq!(analyze_flat("AABBB"), vec![1, 3, 'B' as u32], "Winner should be stored as its ASCII code");
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn test_ffi_round_trip() {
+    use sales_sprint_log_analyzer::ffi::{sssla_analyze, sssla_free};
+    use std::ffi::CString;
+
+    let decode = |log: &[u8]| {
+        let log = CString::new(log).unwrap();
+        let mut len = usize::MAX;
+        let ptr = unsafe { sssla_analyze(log.as_ptr(), &mut len) };
+        let flat = unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+        unsafe { sssla_free(ptr, len) };
+        flat.chunks_exact(3)
+            .map(|c| Outcome::new(c[0] as usize, c[1] as usize, char::from_u32(c[2]).unwrap()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(decode(b"AA!BBB"), analyze_sales_sprints("AABBB"), "FFI outcomes should match the Rust analysis");
+    assert_eq!(decode(&[0xff, b'A', 0xc3, b'A']), analyze_sales_sprints("AA"), "Invalid UTF-8 should be ignored");
+    assert!(decode(b"AABB").is_empty(), "No outcomes should give an empty array");
+
+    let mut len = usize::MAX;
+    let ptr = unsafe { sssla_analyze(std::ptr::null(), &mut len) };
+    assert!(ptr.is_null() && len == 0, "Null input should give a null, empty result");
+    unsafe { sssla_free(ptr, len) };
+}
+
+#[test]
+fn test_win_ratio() {
+    let narrow = DetailedOutcome { outcome: Outcome::new(3, 1, 'A'), a_sprints: 2, b_sprints: 1 };
+    assert_eq!(narrow.win_ratio(), 2.0 / 3.0, "A winning 2 of 3 sprints should give 2/3");
+    for detailed in analyze_sales_sprints_detailed("AAAA") {
+        assert_eq!(detailed.win_ratio(), 1.0, "Sweeps should have a ratio of 1");
+    }
+}
+
+#[test]
+fn test_clean_with_map_offsets() {
+    assert_eq!(clean_with_map("A!B@A"), (b"ABA".to_vec(), vec![0, 2, 4]), "Offsets should point at the raw competitor bytes");
+    let log = "\u{00E1}A\u{00E9}B";
+    let (cleaned, offsets) = clean_with_map(log);
+    assert_eq!(cleaned, clean_log(log), "Cleaned bytes should match clean_log");
+    assert_eq!(offsets, vec![2, 5], "Offsets should be byte offsets past multi-byte chars");
+}
+
+#[test]
+fn test_common_structures_ignore_winner() {
+    let mixed = analyze_sales_sprints("AABBB");
+    let identical = analyze_sales_sprints("AAA");
+    assert_eq!(mixed, vec![Outcome::new(1, 3, 'B')], "Fixture should only read as (1, 3, 'B')");
+    assert_eq!(common_structures(&mixed, &identical), vec![(1, 3)], "Only (1, 3) is shared, and winners differ");
+    assert_eq!(common_structures(&identical, &analyze_sales_sprints("B!BB")), vec![(1, 3), (3, 1)], "Same length identical logs share every structure");
+    assert!(common_structures(&identical, &analyze_sales_sprints("AAAA")).is_empty(), "Different lengths share nothing");
+}
+
+#[test]
+fn test_allow_early_accepts_trailing_characters() {
+    assert!(analyze_sales_sprints("AAB").is_empty(), "The trailing 'B' should rule out every default reading");
+    assert_eq!(
+        analyze_allow_early("AAB"),
+        vec![Outcome::new(1, 1, 'A'), Outcome::new(1, 2, 'A'), Outcome::new(2, 1, 'A')],
+        "A should be able to clinch before the trailing 'B'"
+    );
+    for log in ["AAAA", "AABBB", "ABAABA", "A!B@A"] {
+        let early = analyze_allow_early(log);
+        for o in analyze_sales_sprints(log) {
+            assert!(early.contains(&o), "Default outcome {:?} of {:?} should also be early-valid", o, log);
+        }
+    }
+    assert!(analyze_allow_early("").is_empty(), "An empty log has no sprints to win");
+}
+
+#[test]
+fn test_sprint_winner_string() {
+    assert_eq!(sprint_winner_string("AABBB"), Some("AABBB".to_string()), "Each t = 1 sprint winner should appear in order");
+    assert_eq!(sprint_winner_string("A!B@A"), Some("ABA".to_string()), "Ignored characters should not appear");
+    assert_eq!(sprint_winner_string("AABB"), None, "A log with no valid outcome should give None");
+}
+
+#[test]
+fn test_analyze_utf16_ignores_other_units() {
+    let units: Vec<u16> = "A\u{00E1}A\u{1F600}B\u{4E2D}BB".encode_utf16().collect();
+    assert_eq!(analyze_utf16(&units, 'A', 'B'), analyze_sales_sprints("AABBB"), "Non-competitor units and surrogate pairs should be ignored");
+    let units: Vec<u16> = "x\u{00E9}\u{00E9}".encode_utf16().collect();
+    assert_eq!(analyze_utf16(&units, '\u{00E9}', 'x'), vec![Outcome::new(1, 2, '\u{00E9}')], "BMP competitors beyond ASCII should work");
+    let units: Vec<u16> = "\u{1F600}\u{1F600}".encode_utf16().collect();
+    assert!(analyze_utf16(&units, '\u{1F600}', 'B').is_empty(), "Competitors outside the BMP are not supported");
+}
+
+#[test]
+fn test_outcomes_won_by_matches_filtered_analysis() {
+    for log in ["AAAA", "AABBB", "ABAABA", "BBAAA", "", &"A".repeat(12)] {
+        for player in ['A', 'B'] {
+            let filtered: Vec<Outcome> = analyze_sales_sprints(log).into_iter().filter(|o| o.winner == player).collect();
+            assert_eq!(outcomes_won_by(log, player), filtered, "Outcomes of {:?} won by {:?} should match", log, player);
+        }
+    }
+    assert!(outcomes_won_by(&"A".repeat(1000), 'B').is_empty(), "B can't win an all-'A' log");
+    assert!(outcomes_won_by("AAAA", 'C').is_empty(), "Non-competitors never win");
+}
+
+#[test]
+fn test_boundaries_agree() {
+    assert!(!boundaries_agree("AAAA", 1, 2, 1), "t = 1 closes its first sprint at 1, t = 2 at 2");
+    assert!(boundaries_agree("AAAA", 1, 2, 0), "Comparing no sprints should trivially agree");
+    assert!(boundaries_agree("ABAB", 2, 2, 5), "A target always agrees with itself");
+    assert!(boundaries_agree("AB", 3, 4, 2), "Targets with no complete sprint should agree");
+}
+
+#[test]
+fn test_winner_counts() {
+    assert_eq!(winner_counts(&"A".repeat(12)), (divisor_count(12), 0), "All-'A' should count every divisor pair for A");
+    assert_eq!(winner_counts("AABBB"), (0, 1), "AABBB should only have B's single-sprint reading");
+    assert_eq!(winner_counts("BAAA!BBB"), (0, 1), "Mixed log should count B's (1, 4) reading");
+    for log in ["ABAABA", "BBBB", ""] {
+        let summary = summarize_streaming(log);
+        assert_eq!(winner_counts(log), (summary.a_wins, summary.b_wins), "Counts should match the summary for {:?}", log);
+    }
+}
+
+#[test]
+fn test_dedup_batch_analyzes_each_cleaned_log_once() {
+    let logs = ["AABBB", "AAAA", "AA!BBB", "AABBB", "A@A@A@A", ""];
+    let expected: Vec<_> = logs.iter().map(|log| analyze_sales_sprints(log)).collect();
+    assert_eq!(dedup_batch(&logs), expected, "Each position should get its own log's outcomes");
+    let mut runs = 0;
+    let results = dedup_batch_with(&logs, |cleaned| {
+        runs += 1;
+        analyze_sales_sprints_bytes(cleaned)
+    });
+    assert_eq!(results, expected, "Injected analysis should give the same results");
+    assert_eq!(runs, 3, "Only AABBB, AAAA and the empty log are distinct once cleaned");
+}
+
+#[test]
+fn test_analyze_circular_finds_rotated_readings() {
+    assert!(analyze_sales_sprints("AAB").is_empty(), "AAB has no reading as written");
+    assert_eq!(analyze_circular("AAB"), vec![(Outcome::new(1, 2, 'A'), 1)], "Rotating to ABA should give A a two-point sprint");
+    let straight: Vec<_> = analyze_sales_sprints("AAAA").into_iter().map(|o| (o, 0)).collect();
+    assert_eq!(analyze_circular("AAAA"), straight, "Readings valid as written should need no rotation");
+    assert!(analyze_circular("").is_empty(), "An empty ring has no readings");
+}
+
+#[test]
+fn test_to_dot_has_a_node_per_outcome() {
+    let dot = to_dot("AAAA");
+    assert!(dot.starts_with("digraph outcomes {") && dot.ends_with("}\n"), "Output should be one DOT digraph");
+    assert!(dot.contains("root [label=\"AAAA\"];"), "Root should be labeled with the log");
+    for (i, o) in analyze_sales_sprints("AAAA").iter().enumerate() {
+        assert!(dot.contains(&format!("o{} [label=\"s={}, t={}\"];", i, o.s, o.t)), "Missing node for {:?}", o);
+        assert!(dot.contains(&format!("root -> o{};", i)), "Outcome {} should hang off the root", i);
+        assert!(dot.contains(&format!("w{} [label=\"A\", shape=box];", i)), "Outcome {} should have a winner leaf", i);
+    }
+    assert_eq!(dot.matches(" -> o").count(), 3, "AAAA should have exactly three outcome nodes");
+    assert!(to_dot("A\"B").contains("root [label=\"A\\\"B\"];"), "Quotes in the log should be escaped");
+}
+
+#[test]
+fn test_balance_point() {
+    assert_eq!(balance_point("AABB"), Some(3), "The second 'B' levels the score");
+    assert_eq!(balance_point("AB!AB"), Some(1), "The first crossover should be reported, in cleaned indices");
+    assert_eq!(balance_point("AAAA"), None, "B never catches up");
+    assert_eq!(balance_point(""), None, "An empty log has no crossover");
+}
+
+#[test]
+fn test_reset_on_noise_aborts_sprints() {
+    assert!(analyze_sales_sprints("AA!AA").contains(&Outcome::new(1, 4, 'A')), "Skipping noise should allow one 4-point sprint");
+    assert_eq!(
+        analyze_reset_on_noise("AA!AA"),
+        vec![Outcome::new(2, 2, 'A'), Outcome::new(4, 1, 'A')],
+        "The '!' should split the log so no 4-point sprint exists"
+    );
+    assert_eq!(analyze_reset_on_noise("AB!AA"), vec![Outcome::new(1, 2, 'A')], "The aborted A-B sprint should count for nobody");
+    assert_eq!(analyze_reset_on_noise("AA!A"), vec![Outcome::new(3, 1, 'A')], "t = 2 leaves a sprint in progress at the end");
+    for log in ["AAAA", "AABBB", "ABAABA"] {
+        assert_eq!(analyze_reset_on_noise(log), analyze_sales_sprints(log), "Noise-free logs should match the default for {:?}", log);
+    }
+}
+
+#[test]
+fn test_outcomes_by_target() {
+    let by_target = outcomes_by_target(&"A".repeat(12));
+    let mut targets: Vec<usize> = by_target.keys().copied().collect();
+    targets.sort();
+    assert_eq!(targets, vec![1, 2, 3, 4, 6, 12], "Every divisor of 12 should be a target");
+    for (t, o) in &by_target {
+        assert_eq!(*o, Outcome::new(12 / t, *t, 'A'), "Target {} should map to its outcome", t);
+    }
+    assert!(outcomes_by_target("AABB").is_empty(), "A log with no outcome should give an empty map");
+}
+
+#[test]
+fn test_analyze_min_length() {
+    assert!(analyze_min_length("AAA", 5).is_empty(), "A 3-point log should be below the minimum");
+    assert_eq!(analyze_min_length("AAAAAA", 5), analyze_sales_sprints("AAAAAA"), "A 6-point log should be analyzed normally");
+    assert!(analyze_min_length("A!!!!A", 5).is_empty(), "Ignored characters should not count toward the minimum");
+    assert_eq!(analyze_min_length("AAAAA", 5).len(), 2, "The minimum should be inclusive");
+}
+
+#[test]
+fn test_outcome_point_totals() {
+    let log = "A".repeat(12);
+    for o in analyze_sales_sprints(&log) {
+        assert_eq!(o.total_points(), Some(competitor_count(&log)), "{:?} should account for every point", o);
+        assert_eq!(o.points_per_sprint(), o.t, "Points per sprint should be the target");
+    }
+    let o = analyze_sales_sprints("AABBB")[0];
+    assert_eq!(o.total_points(), Some(3), "Only the winner's points are counted in a mixed log");
+    assert_eq!(Outcome::new(usize::MAX, 2, 'A').total_points(), None, "An overflowing product should be None rather than wrap or panic");
+}
+
+#[test]
+fn test_upsets_against() {
+    assert!(upsets_against(&"A".repeat(12), 'A').is_empty(), "A can't be upset in an all-'A' log");
+    assert_eq!(upsets_against("AABBB", 'A'), vec![Outcome::new(1, 3, 'B')], "B's reading should be an upset when A is expected");
+    assert!(upsets_against("AABBB", 'B').is_empty(), "No reading goes against B");
+    assert_eq!(upsets_against("BBBB", 'C'), analyze_sales_sprints("BBBB"), "Every outcome is an upset for a non-competitor");
+}
+
+#[test]
+fn test_modal_target() {
+    // AAAA: t in {1, 2, 4}; AAAAAA: {1, 2, 3, 6}; AABBB: {3}; BBB: {1, 3}.
+    // t = 1 and t = 3 both appear three times, so the smaller wins.
+    assert_eq!(modal_target(&["AAAA", "AAAAAA", "AABBB", "BBB"]), Some(1), "Ties should go to the smallest target");
+    assert_eq!(modal_target(&["AABBB", "BBB", "AAA"]), Some(3), "t = 3 appears in every log");
+    assert_eq!(modal_target(&["AABB", ""]), None, "No outcomes should give no mode");
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_assert_no_early_termination_accepts_valid_outcomes() {
+    use sales_sprint_log_analyzer::testing::assert_no_early_termination;
+
+    for log in ["A", "AAAA", "AABBB", "ABAABA", "A!B@A", &"A".repeat(12)] {
+        assert_no_early_termination(log, &analyze_sales_sprints(log));
+    }
+}
+
+#[cfg(feature = "testing")]
+#[test]
+#[should_panic(expected = "should end at the end of the log")]
+fn test_assert_no_early_termination_rejects_early_end() {
+    // Two sprints of target 2 close at 'AA' | 'AA', leaving the last 'A' over.
+    sales_sprint_log_analyzer::testing::assert_no_early_termination("AAAAA", &[Outcome::new(2, 2, 'A')]);
+}
+
+#[test]
+fn test_reach_or_exceed_with_step() {
+    assert_eq!(
+        analyze_reach_or_exceed("AAAA", 2),
+        Some(vec![
+            Outcome::new(1, 7, 'A'),
+            Outcome::new(1, 8, 'A'),
+            Outcome::new(2, 3, 'A'),
+            Outcome::new(2, 4, 'A'),
+            Outcome::new(4, 1, 'A'),
+            Outcome::new(4, 2, 'A'),
+        ]),
+        "Each pair of targets should share the boundaries of one character target"
+    );
+    assert_eq!(analyze_reach_or_exceed("AABBB", 2), Some(vec![Outcome::new(1, 5, 'B'), Outcome::new(1, 6, 'B')]), "B should need 3 characters for t = 5 and 6");
+    assert_eq!(analyze_reach_or_exceed("ABAABA", 1), Some(analyze_sales_sprints("ABAABA")), "A step of 1 should match the default rule");
+    assert_eq!(analyze_reach_or_exceed("AAAA", 0), Some(vec![]), "A step of 0 never closes a sprint");
+}
+
+#[test]
+fn test_structure_similarity() {
+    assert_eq!(structure_similarity("AAAA", "AAAA"), 1.0, "A log should match itself");
+    assert_eq!(structure_similarity("AAAA", "B!BBB"), 1.0, "Winners should be ignored");
+    assert_eq!(structure_similarity("AAAA", "AABBB"), 0.0, "AAAA and AABBB share no (s, t)");
+    assert_eq!(structure_similarity("AAA", "AABBB"), 0.5, "AAA reads as (1, 3) and (3, 1), AABBB only as (1, 3)");
+}
+
+#[test]
+fn test_analyze_timed_reports_incomplete_runs() {
+    let log = "A".repeat(720_720);
+    let (outcomes, complete) = analyze_timed(&log, Duration::ZERO);
+    assert!(!complete, "A zero budget should not finish");
+    assert!(outcomes.len() < divisor_count(720_720), "An unfinished run should miss outcomes");
+    let (outcomes, complete) = analyze_timed("ABAABA", Duration::from_secs(60));
+    assert!(complete, "A generous budget should finish");
+    assert_eq!(outcomes, analyze_sales_sprints("ABAABA"), "A finished run should match the default analysis");
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_analyze_gz_file_matches_uncompressed() {
+    use std::io::Write;
+
+    let log = "AB!".repeat(100) + &"A".repeat(300);
+    let path = temp_path("analyze_gz_file.log.gz");
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(log.as_bytes()).unwrap();
+    std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+    let result = sales_sprint_log_analyzer::analyze_gz_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(result.unwrap(), analyze_sales_sprints(&log), "Decompressed analysis should match the plain log");
+}
+
+#[test]
+fn test_longest_run() {
+    assert_eq!(longest_run("AABBB"), ('B', 3), "The three 'B's should be the longest run");
+    assert_eq!(longest_run("AA!AB"), ('A', 3), "Ignored characters should not break a run");
+    assert_eq!(longest_run("ABBAA"), ('B', 2), "Ties should go to the earliest run");
+    assert_eq!(longest_run("!?"), ('\0', 0), "No competitors should give an empty run");
+}
+
+#[test]
+fn test_analyze_with_ranges_covers_cleaned_log() {
+    let ranged = analyze_with_ranges("AAAA");
+    assert!(ranged.contains(&(Outcome::new(2, 2, 'A'), vec![0..2, 2..4])), "(2, 2) should split AAAA in half");
+    for log in ["AAAA", "AABBB", "A!B@A", "ABAABA"] {
+        let n = competitor_count(log);
+        for (o, ranges) in analyze_with_ranges(log) {
+            assert_eq!(ranges.len(), o.s, "{:?} should have one range per sprint", o);
+            assert_eq!(ranges.first().map(|r| r.start), Some(0), "Ranges should start at 0");
+            assert_eq!(ranges.last().map(|r| r.end), Some(n), "Ranges should end at the cleaned length");
+            assert!(ranges.windows(2).all(|w| w[0].end == w[1].start), "Ranges should be contiguous");
+        }
+    }
+}
+
+#[test]
+fn test_truncatable_analyzer_matches_prefixes() {
+    let mut analyzer = TruncatableAnalyzer::new("AAAA");
+    for len in (0..4).rev() {
+        assert_eq!(analyzer.pop(), Some('A'), "Each pop should remove an 'A'");
+        assert_eq!(analyzer.outcomes(), analyze_sales_sprints(&"A".repeat(len)), "Outcomes after popping to {} should match a fresh analysis", len);
+    }
+    assert_eq!(analyzer.pop(), None, "Popping an empty log should give None");
+
+    let log = "AB!AAB?B";
+    let cleaned = "ABAABB";
+    let mut analyzer = TruncatableAnalyzer::new(log);
+    assert_eq!(analyzer.outcomes(), analyze_sales_sprints(log), "The untouched analyzer should match the log");
+    for len in (0..cleaned.len()).rev() {
+        assert_eq!(analyzer.pop(), cleaned[len..].chars().next(), "Pops should come off the cleaned log's end");
+        assert_eq!(analyzer.outcomes(), analyze_sales_sprints(&cleaned[..len]), "Outcomes should match the prefix of length {}", len);
+    }
+}
+
+#[test]
+fn test_match_categories() {
+    let detailed = |a_sprints, b_sprints| DetailedOutcome { outcome: Outcome::new(a_sprints + b_sprints, 1, 'A'), a_sprints, b_sprints };
+    assert_eq!(detailed(3, 0).category(), MatchCategory::Sweep, "No sprints for the loser should be a sweep");
+    assert_eq!(detailed(1, 0).category(), MatchCategory::Sweep, "A 1-0 score is still a sweep");
+    assert_eq!(detailed(4, 2).category(), MatchCategory::Decisive, "A margin of 2 with a loser's sprint should be decisive");
+    assert_eq!(detailed(3, 2).category(), MatchCategory::Close, "A margin of 1 should be close");
+    for d in analyze_sales_sprints_detailed("AAAA") {
+        assert_eq!(d.category(), MatchCategory::Sweep, "Default outcomes are always sweeps");
+    }
+}
+
+#[test]
+fn test_analyze_with_header() {
+    assert_eq!(analyze_with_header("#players:H,V\nHHVVV").unwrap(), vec![Outcome::new(1, 3, 'V')], "The header should name the competitors");
+    assert_eq!(analyze_with_header("#players:H,V\r\nHHHH").unwrap(), analyze_with_header("#players:H,V\nHHHH").unwrap(), "CRLF headers should be accepted");
+    assert_eq!(analyze_with_header("AABBB").unwrap(), analyze_sales_sprints("AABBB"), "Without a header 'A' and 'B' should be used");
+    for bad in ["#players:HV\nHH", "#players:H,\nHH", "#players:HH,V\nHH", "#players:H,H\nHH", "#players\nHH"] {
+        assert_eq!(
+            analyze_with_header(bad),
+            Err(SprintError::InvalidHeader { header: bad.split('\n').next().unwrap().to_string() }),
+            "Malformed header in {:?} should be rejected",
+            bad
+        );
+    }
+}
+
+#[test]
+fn test_dedup_outcomes_iter_yields_each_once() {
+    let merged = analyze_sales_sprints("AAAA").into_iter().chain(analyze_sales_sprints("A!AAA")).chain(analyze_sales_sprints("BBBB"));
+    let deduped: Vec<Outcome> = dedup_outcomes_iter(merged).collect();
+    let mut expected = analyze_sales_sprints("AAAA");
+    expected.extend(analyze_sales_sprints("BBBB"));
+    assert_eq!(deduped, expected, "Repeats should be dropped and first-seen order kept");
+}
+
+#[test]
+fn test_analyze_sliding_tallies_window_majorities() {
+    assert_eq!(analyze_sliding("AABAB", 3), vec![(1, 0), (2, 0), (2, 1)], "Windows AAB, ABA, BAB should go A, A, B");
+    assert_eq!(analyze_sliding("AA!BB", 2), vec![(1, 0), (1, 0), (1, 1)], "The level AB window should count for neither");
+    assert_eq!(analyze_sliding("AAAA", 1), vec![(1, 0), (2, 0), (3, 0), (4, 0)], "Every single-character window is won");
+    assert!(analyze_sliding("AB", 3).is_empty(), "A window longer than the log should give nothing");
+    assert!(analyze_sliding("AB", 0).is_empty(), "Empty windows should give nothing");
+}
+
+#[test]
+fn test_most_dominant() {
+    // "BAAAA" reads as (1, 4, 'A') with margin 1 and (2, 2, 'A') with margin 2.
+    assert_eq!(simplify("BAAAA"), vec![(1, 4, 'A'), (2, 2, 'A')], "Fixture should have two readings");
+    assert_eq!(most_dominant("BAAAA").map(|d| d.outcome), Some(Outcome::new(2, 2, 'A')), "The two-sprint reading has the wider margin");
+    let all_a = most_dominant("AAAAAA").unwrap();
+    assert_eq!((all_a.outcome, all_a.margin()), (Outcome::new(6, 1, 'A'), 6), "All-'A' should pick t = 1 with margin n");
+    assert_eq!(most_dominant("AABB"), None, "No outcome should give None");
+}
+
+#[test]
+fn test_analyze_to_channel() {
+    let log = "A".repeat(360);
+    let (tx, rx) = std::sync::mpsc::channel();
+    let producer = {
+        let log = log.clone();
+        std::thread::spawn(move || analyze_to_channel(&log, tx))
+    };
+    let mut received: Vec<Outcome> = rx.iter().collect();
+    producer.join().unwrap();
+    received.sort();
+    assert_eq!(received, analyze_sales_sprints(&log), "Every outcome should arrive over the channel");
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    drop(rx);
+    analyze_to_channel(&log, tx);
+}
+
+#[test]
+fn test_step_points_resolves_simultaneous_reach() {
+    // Scored two characters at a time, "ABAB" is two level steps.
+    assert_eq!(analyze_step_points("ABAB", 2, 'A'), vec![Outcome::new(1, 2, 'A'), Outcome::new(2, 1, 'A')], "Ties should go to A");
+    assert_eq!(analyze_step_points("ABAB", 2, 'B'), vec![Outcome::new(1, 2, 'B'), Outcome::new(2, 1, 'B')], "Ties should go to B");
+    assert!(analyze_sales_sprints("ABAB").is_empty(), "Character by character, ABAB has no reading");
+    for log in ["AAAA", "AABBB", "ABAABA", "A!B@A"] {
+        assert_eq!(analyze_step_points(log, 1, 'A'), analyze_sales_sprints(log), "Single-character steps should match the default for {:?}", log);
+    }
+    assert!(analyze_step_points("AAAA", 0, 'A').is_empty(), "A step of 0 scores nothing");
+}
+
+#[test]
+fn test_check_decomposition_reports_each_condition() {
+    let correct = check_decomposition("AA!BBB", 1, 3, 'B');
+    assert!(correct.is_valid(), "(1, 3, 'B') is a valid reading: {:?}", correct);
+
+    let wrong_length = check_decomposition("AAAA", 2, 3, 'A');
+    assert!(!wrong_length.length_ok, "2 * 3 is not A's 4 points");
+    assert_eq!(wrong_length.length_message, "s * t = 6 does not match the 4 points 'A' scored", "Length message should give both counts");
+
+    let wrong_simulation = check_decomposition("BBA", 1, 2, 'B');
+    assert!(wrong_simulation.length_ok && wrong_simulation.winner_ok, "B scores 2 and wins the only sprint");
+    assert!(!wrong_simulation.simulation_ok, "The trailing 'A' is left over");
+    assert_eq!(wrong_simulation.simulation_message, "target 2 leaves 1 points after the last complete sprint", "Simulation message should give the leftover");
+
+    let wrong_winner = check_decomposition("AAAA", 2, 2, 'B');
+    assert!(wrong_winner.simulation_ok, "Target 2 does split AAAA into 2 sprints");
+    assert!(!wrong_winner.winner_ok, "B wins no sprint");
+    assert_eq!(wrong_winner.winner_message, "sprint 1 is won by 'A', not 'B'", "Winner message should name the first lost sprint");
+    assert!(!wrong_winner.is_valid(), "Any failed check should make the reading invalid");
+}
+
+#[test]
+fn test_detailed_outcomes_to_tsv() {
+    let tsv = detailed_outcomes_to_tsv(&analyze_sales_sprints_detailed("AABBB"));
+    assert_eq!(tsv, "s\tt\twinner\ta_sprints\tb_sprints\tmargin\n1\t3\tB\t0\t1\t1\n", "Header and one row should be written");
+    let narrow = DetailedOutcome { outcome: Outcome::new(3, 1, 'A'), a_sprints: 2, b_sprints: 1 };
+    assert!(detailed_outcomes_to_tsv(&[narrow]).ends_with("3\t1\tA\t2\t1\t1\n"), "Margin should be computed from the sprint counts");
+    assert_eq!(detailed_outcomes_to_tsv(&[]).lines().count(), 1, "No outcomes should leave just the header");
+}
+
+#[test]
+fn test_winner_entropy() {
+    assert_eq!(winner_entropy(&"A".repeat(12)), 0.0, "All outcomes won by A should have no entropy");
+    assert_eq!(winner_entropy("AABBB"), 0.0, "A single outcome should have no entropy");
+    assert_eq!(winner_entropy(""), 0.0, "No outcomes should have no entropy");
+}
+
+#[test]
+fn test_analysis_plan_matches_run() {
+    for n in [1, 12, 360] {
+        let plan = analysis_plan(&"A".repeat(n));
+        assert_eq!(plan.competitor_count, n, "Plan should count every point");
+        assert_eq!(plan.candidate_count, divisor_count(n), "Plan should have one candidate per divisor of {}", n);
+    }
+    for log in ["AABBB", "ABAABA", "A!B@A", &"AAB".repeat(40)] {
+        let plan = analysis_plan(log);
+        let (_, stats) = analyze_with_stats(log);
+        assert_eq!(plan.candidate_count, stats.candidates, "Planned candidates should match the run for {:?}", log);
+        assert!(plan.worst_case_bytes_scanned >= stats.bytes_scanned, "Estimate should bound the run for {:?}", log);
+    }
+}
+
+#[test]
+fn test_analyze_detailed_iter_matches_eager() {
+    for log in ["", "AAAA", "AABBB", "ABAABA", "BAAAA", &"B".repeat(36)] {
+        let lazy: Vec<DetailedOutcome> = analyze_detailed_iter(log).collect();
+        assert_eq!(lazy, analyze_sales_sprints_detailed(log), "Lazy details should match for {:?}", log);
+    }
+    let first = analyze_detailed_iter(&"A".repeat(12)).next().map(|d| d.outcome);
+    assert_eq!(first, Some(Outcome::new(1, 12, 'A')), "Items should come in sorted order");
+}
+
+#[cfg(feature = "bitvec")]
+#[test]
+fn test_analyze_bits_matches_chars() {
+    use bitvec::prelude::*;
+
+    for log in ["AABBB", "AAAA", "ABAABA", ""] {
+        let bits: BitVec = log.chars().map(|c| c == 'B').collect();
+        assert_eq!(sales_sprint_log_analyzer::analyze_bits(&bits), analyze_sales_sprints(log), "Bit analysis should match for {:?}", log);
+    }
+}
+
+#[test]
+fn test_median_outcome() {
+    let log = "A".repeat(12);
+    assert_eq!(median_outcome(&log), Some(analyze_sales_sprints(&log)[2]), "Six outcomes should give the third");
+    assert_eq!(median_outcome(&log), Some(Outcome::new(3, 4, 'A')), "The third 12-'A' outcome is (3, 4)");
+    assert_eq!(median_outcome("AAAA"), Some(Outcome::new(2, 2, 'A')), "Three outcomes should give the middle one");
+    assert_eq!(median_outcome("AABB"), None, "No outcome should give None");
+}
+
+#[test]
+fn test_analyze_weighted_input_drops_low_confidence() {
+    let events = [('B', 0.3), ('A', 0.9), ('!', 0.9), ('A', 0.5)];
+    assert_eq!(analyze_weighted_input(&events, 0.0, 'A', 'B'), analyze_sales_sprints("BAA"), "Nothing should be dropped at threshold 0");
+    assert_eq!(analyze_weighted_input(&events, 0.5, 'A', 'B'), analyze_sales_sprints("AA"), "The uncertain 'B' should be dropped, the threshold itself kept");
+    assert!(analyze_weighted_input(&events, 0.5, 'A', 'B').contains(&Outcome::new(2, 1, 'A')), "Dropping the 'B' should make (2, 1) valid");
+    assert!(analyze_weighted_input(&events, 1.0, 'A', 'B').is_empty(), "Dropping everything should leave no outcome");
+}
+
+#[test]
+fn test_winners_per_sprint_count() {
+    let by_s = winners_per_sprint_count("BAAAA");
+    assert_eq!(by_s.keys().copied().collect::<Vec<_>>(), vec![1, 2], "BAAAA reads as one or two sprints");
+    assert!(by_s.values().all(|w| *w == HashSet::from(['A'])), "A should win at every sprint count");
+    let by_s = winners_per_sprint_count("AA!BBB");
+    assert_eq!(by_s.len(), 1, "AABBB only reads as one sprint");
+    assert_eq!(by_s[&1], HashSet::from(['B']), "B should win the single sprint");
+    assert!(winners_per_sprint_count("AABB").is_empty(), "No outcome should give an empty map");
+}
+
+#[test]
+fn test_prefix_analyzer_matches_fresh_analysis() {
+    let mut analyzer = PrefixAnalyzer::new("AAAA");
+    for k in 0..=4 {
+        assert_eq!(analyzer.outcomes_for_prefix(k), analyze_sales_sprints(&"AAAA"[..k]), "Prefix of length {} should match", k);
+    }
+    let log = "BA!AB?A\u{00E9}A";
+    let mut analyzer = PrefixAnalyzer::new(log);
+    for k in (0..=log.len()).rev().filter(|&k| log.is_char_boundary(k)) {
+        assert_eq!(analyzer.outcomes_for_prefix(k), analyze_sales_sprints(&log[..k]), "Prefix {:?} should match", &log[..k]);
+    }
+    assert_eq!(analyzer.outcomes_for_prefix(usize::MAX), analyze_sales_sprints(log), "An overlong prefix should be the whole log");
+}
+
+#[test]
+fn test_is_balanced() {
+    assert!(is_balanced("AABB"), "Two points each should be balanced");
+    assert!(is_balanced("A!B?BA"), "Ignored characters should not count");
+    assert!(!is_balanced("AAB"), "Two to one should not be balanced");
+    assert!(is_balanced(""), "An empty log is trivially balanced");
+    assert_eq!(is_single_sprint_match("AABB"), None, "A balanced log has no whole-log sprint");
+}
+
+#[test]
+fn test_analyze_sorted_by_winner() {
+    for log in ["AABBB", "BAAAA", "A!B@A", &"B".repeat(12)] {
+        let sorted = analyze_sorted_by_winner(log);
+        let mut expected = analyze_sales_sprints(log);
+        expected.sort_by_key(|o| (o.winner, o.s, o.t));
+        assert_eq!(sorted, expected, "Outcomes of {:?} should be ordered by winner, s, t", log);
+        let first_b = sorted.iter().position(|o| o.winner == 'B').unwrap_or(sorted.len());
+        assert!(sorted[first_b..].iter().all(|o| o.winner == 'B'), "No 'A'-won outcome should follow a 'B'-won one in {:?}", log);
+    }
+    assert_eq!(analyze_sorted_by_winner("AABBB"), vec![Outcome::new(1, 3, 'B')], "AABBB's only reading is B's");
+}
+
+#[test]
+fn test_sprint_sequence_hash() {
+    let ab = sprint_sequence_hash("AABB", 2);
+    assert!(ab.is_some(), "AABB splits cleanly into sprints of target 2");
+    assert_eq!(sprint_sequence_hash("ABA!ABB", 2), ab, "A then B at target 2 should hash the same as AABB");
+    assert_ne!(sprint_sequence_hash("BBAA", 2), ab, "B then A should hash differently");
+    assert_eq!(sprint_sequence_hash("AABBB", 2), None, "A trailing partial sprint has no sequence");
+    assert_eq!(sprint_sequence_hash("AA", 0), None, "Target 0 has no sprints");
+    assert_eq!(sprint_sequence_hash("", 1), None, "An empty log has no sequence");
+}
+
+#[test]
+fn test_analyze_classified() {
+    assert_eq!(analyze_classified(""), AnalysisResult::Empty, "An empty log should be Empty");
+    assert_eq!(analyze_classified("!?"), AnalysisResult::Empty, "A log of only ignored characters has no points");
+    assert_eq!(analyze_classified("AABB"), AnalysisResult::NoValidDecomposition, "AABB has points but no outcome");
+    assert_eq!(analyze_classified("AAAA"), AnalysisResult::Outcomes(analyze_sales_sprints("AAAA")), "AAAA should carry its outcomes");
+}
+
+#[test]
+fn test_analyze_with_predicate() {
+    let win_by_two = |a: usize, b: usize| a >= 2 && a >= b + 2 || b >= 2 && b >= a + 2;
+    assert_eq!(analyze_with_predicate("ABABAA", win_by_two), vec![Outcome::new(1, 4, 'A')], "A pulls two clear at 4-2");
+    assert_eq!(analyze_with_predicate("ABAAABAA", win_by_two), vec![Outcome::new(2, 3, 'A')], "Two sprints won 3-1");
+    assert_eq!(analyze_with_predicate("A!B!BB", win_by_two), vec![Outcome::new(1, 3, 'B')], "B pulls two clear at 3-1");
+    assert_eq!(analyze_with_predicate("AABAA", win_by_two), vec![], "The second sprint is never won by two");
+    assert_eq!(analyze_with_predicate("AABAA", |a, b| a == 2 || b == 2), vec![Outcome::new(2, 2, 'A')], "Reaching two closes the second sprint at 2-1");
+    assert_eq!(analyze_with_predicate("AAABAA", win_by_two), vec![], "Sprints won with different point totals have no single t");
+    for t in 1..=12 {
+        let expected: Vec<Outcome> = analyze_sales_sprints(&"A".repeat(12)).into_iter().filter(|o| o.t == t).collect();
+        assert_eq!(analyze_with_predicate(&"A".repeat(12), |a, b| a == t || b == t), expected, "The reach-t predicate should match the default rule at t={}", t);
+    }
+}
+
+#[test]
+fn test_target_parity_counts() {
+    assert_eq!(target_parity_counts(&"A".repeat(12)), (4, 2), "Targets 2, 4, 6, 12 are even and 1, 3 odd");
+    assert_eq!(target_parity_counts("AABBB"), (0, 1), "AABBB's only target is 3");
+    assert_eq!(target_parity_counts(""), (0, 0), "An empty log has no outcomes");
+}
+
+#[test]
+fn test_analyze_with_progress() {
+    for log in [&"A".repeat(12), "AABBB", "ABAB", ""] {
+        let mut progress = Vec::new();
+        let outcomes = analyze_with_progress(log, |p| progress.push(p));
+        assert_eq!(outcomes, analyze_sales_sprints(log), "Progress reporting should not change the outcomes of {:?}", log);
+        assert!(progress.windows(2).all(|w| w[0] < w[1]), "Progress for {:?} should strictly increase: {:?}", log, progress);
+        assert_eq!(progress.last(), Some(&1.0), "Progress for {:?} should end at 1.0", log);
+    }
+}
+
+#[test]
+fn test_decisiveness() {
+    assert_eq!(decisiveness(&"A".repeat(12)), 1.0, "Every reading of an all-A log is a sweep");
+    assert_eq!(decisiveness("AABBB"), 1.0, "AABBB's only reading is a sweep");
+    assert!(decisiveness("ABAB") < decisiveness(&"A".repeat(12)), "A balanced log should score lower than a one-sided one");
+    assert_eq!(decisiveness(""), 0.0, "An empty log has no outcomes to be decisive about");
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_outcomes_to_arrow() {
+    use arrow::array::{Array, StringArray, UInt32Array};
+    use arrow::datatypes::DataType;
+
+    let batch = sales_sprint_log_analyzer::outcomes_to_arrow(&analyze_sales_sprints(&"A".repeat(12)));
+    let schema = batch.schema();
+    let fields: Vec<(&str, &DataType)> = schema.fields().iter().map(|f| (f.name().as_str(), f.data_type())).collect();
+    assert_eq!(fields, vec![("s", &DataType::UInt32), ("t", &DataType::UInt32), ("winner", &DataType::Utf8)], "Schema should be s, t, winner");
+    assert_eq!(batch.num_rows(), 6, "There should be one row per outcome");
+    let s = batch.column(0).as_any().downcast_ref::<UInt32Array>().unwrap();
+    let t = batch.column(1).as_any().downcast_ref::<UInt32Array>().unwrap();
+    let winner = batch.column(2).as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!((0..s.len()).map(|i| s.value(i)).collect::<Vec<_>>(), vec![1, 2, 3, 4, 6, 12], "s column should hold each outcome's s");
+    assert_eq!((0..t.len()).map(|i| t.value(i)).collect::<Vec<_>>(), vec![12, 6, 4, 3, 2, 1], "t column should hold each outcome's t");
+    assert!((0..winner.len()).all(|i| winner.value(i) == "A"), "Every winner should be A");
+}
+
+#[test]
+fn test_first_invalid_context() {
+    assert_eq!(first_invalid_context("AABB!BBAA", 2), Some((4, "BB[!]BB".to_string())), "The context should show two characters either side");
+    assert_eq!(first_invalid_context("A?B", 5), Some((1, "A[?]B".to_string())), "The context should stop at the ends of the log");
+    assert_eq!(first_invalid_context("éAB!", 1), Some((0, "[é]A".to_string())), "The first invalid character may be multi-byte");
+    assert_eq!(first_invalid_context("AéB", 0), Some((1, "[é]".to_string())), "A radius of 0 shows only the character");
+    assert_eq!(first_invalid_context("AABB", 3), None, "A clean log has nothing to report");
+}
+
+#[test]
+fn test_analyze_dual_pairs() {
+    let (ab, cd) = analyze_dual_pairs("ACADBDBD", ('A', 'B'), ('C', 'D'));
+    assert_eq!(ab, analyze_sales_sprints("AABB"), "A vs B should be read from AABB alone");
+    assert_eq!(cd, vec![Outcome::new(1, 3, 'D')], "C vs D should be read from CDDD alone");
+    let (ab, cd) = analyze_dual_pairs("ACACAC", ('A', 'B'), ('C', 'D'));
+    assert_eq!(ab, analyze_sales_sprints("AAA"), "A vs B should ignore the C characters");
+    assert_eq!(cd, vec![Outcome::new(1, 3, 'C'), Outcome::new(3, 1, 'C')], "C vs D should ignore the A characters");
+}
+
+#[test]
+fn test_last_lead_change() {
+    assert_eq!(Outcome::new(5, 1, 'A').last_lead_change("BBAAA"), Some(4), "A only goes ahead in the last sprint");
+    assert_eq!(Outcome::new(3, 2, 'A').last_lead_change("BBAAAA"), Some(2), "B takes the first sprint and A leads after the third");
+    assert_eq!(Outcome::new(3, 1, 'A').last_lead_change("ABA"), Some(2), "A's early lead is lost at 1-1 and retaken");
+    assert_eq!(Outcome::new(4, 3, 'A').last_lead_change(&"A".repeat(12)), None, "A sweep leads throughout");
+    assert_eq!(Outcome::new(2, 1, 'B').last_lead_change("AA"), None, "B never leads");
+    assert_eq!(Outcome::new(4, 1, 'A').last_lead_change("BBAAA"), None, "The wrong sprint count is not a reading of the log");
+    assert_eq!(Outcome::new(3, 2, 'A').last_lead_change("BBAAAAA"), None, "A trailing partial sprint is not a reading of the log");
+    assert_eq!(Outcome::new(3, 1, 'B').last_lead_change("ABA"), None, "The wrong winner is not a reading of the log");
+    assert_eq!(Outcome::new(5, 1, 'A').last_lead_change("BAAAB"), Some(2), "The majority winner need not take the last sprint");
+}
+
+#[test]
+fn test_analyze_sales_sprints_with_capacity() {
+    for log in [&"A".repeat(12), "AABBB", "ABAB", ""] {
+        let cap = candidate_count(log);
+        let outcomes = analyze_sales_sprints_with_capacity(log, cap);
+        assert_eq!(outcomes, analyze_sales_sprints(log), "Preallocating should not change the outcomes of {:?}", log);
+        assert!(outcomes.capacity() >= cap, "The vector for {:?} should hold at least {} outcomes", log, cap);
+    }
+    assert!(analyze_sales_sprints_with_capacity("AB", 64).capacity() >= 64, "Capacity should be honoured even with no outcomes");
+}
+
+#[test]
+fn test_outcome_as_fields() {
+    let fields = Outcome::new(2, 2, 'A').as_fields();
+    let expected: Vec<(&str, &str)> = vec![("s", "2"), ("t", "2"), ("winner", "A")];
+    assert_eq!(fields.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect::<Vec<_>>(), expected, "The map should hold s, t and winner as strings");
+    assert_eq!(Outcome::new(1, 12, 'B').as_fields()["t"], "12", "Multi-digit values should be written in full");
+}
+
+#[test]
+fn test_running_winners() {
+    assert_eq!(running_winners("AABBBA", 2), vec!['A', 'B'], "The trailing 'BA' is a partial sprint and should be ignored");
+    assert_eq!(running_winners("AABBBAA", 2), vec!['A', 'B', 'A'], "'BAA' completes a third sprint at 2-1");
+    assert_eq!(running_winners("AB!BA", 3), vec![], "No sprint reaches 3");
+    assert_eq!(running_winners("AA", 0), vec![], "Target 0 has no sprints");
+}
+
+#[test]
+fn test_canonical_log_rejects_overflowing_outcomes() {
+    assert_eq!(canonical_log(&[Outcome::new(usize::MAX, 2, 'A')]), None, "An overflowing s * t should be rejected");
+    assert_eq!(canonical_log(&[Outcome::new(1, usize::MAX, 'A'), Outcome::new(usize::MAX, 1, 'A')]), None, "A log too long to allocate should be rejected");
+    assert_eq!(canonical_log(&[Outcome::new(1, 4, 'A'), Outcome::new(1, 3, 'A')]), None, "Outcomes for different lengths should be rejected");
+    assert_eq!(canonical_log(&analyze_sales_sprints("BBBB")), Some("BBBB".to_string()), "A complete set should still rebuild its log");
+}
+
+#[test]
+fn test_reset_on_noise_large_logs() {
+    let log = "A".repeat(30_000);
+    assert_eq!(analyze_reset_on_noise(&log), analyze_sales_sprints(&log), "Without noise, resetting should match the default analysis");
+    let noisy = "AAA!".repeat(10_000);
+    assert_eq!(analyze_reset_on_noise(&noisy), vec![Outcome::new(10_000, 2, 'A'), Outcome::new(10_000, 3, 'A'), Outcome::new(30_000, 1, 'A')], "Each run of three A's should hold one sprint of 2 or 3, or three of 1, with leftovers aborted by the '!'");
+}
+
+#[test]
+fn test_reach_or_exceed_rejects_overflowing_step() {
+    assert_eq!(analyze_reach_or_exceed("AA", usize::MAX), None, "A step whose target bound overflows should be reported, not answered with no outcomes");
+    assert_eq!(analyze_reach_or_exceed("A!AB", usize::MAX / 2), None, "Three points at half of usize::MAX each should overflow too");
+    assert_eq!(analyze_reach_or_exceed("", usize::MAX), Some(vec![]), "An empty log has no targets to overflow");
+}
+
+#[test]
+fn test_step_points_large_logs() {
+    let log = "A".repeat(30_000);
+    assert_eq!(analyze_step_points(&log, 1, 'A'), analyze_sales_sprints(&log), "Single-character steps should match the default on a long log");
+    let tied = "AB".repeat(15_000);
+    let expected: Vec<Outcome> = divisor_pairs(15_000).into_iter().map(|(s, t)| Outcome::new(s, t, 'A')).collect();
+    assert_eq!(analyze_step_points(&tied, 2, 'A'), expected, "Every step ties, so target t closes a sprint every t steps");
+}
+
+#[test]
+fn test_check_decomposition_overflowing_length() {
+    let check = check_decomposition("AAAA", usize::MAX, 2, 'A');
+    assert!(!check.length_ok, "An overflowing s * t should fail the length check");
+    assert!(check.length_message.contains("overflows"), "The message should say the product overflows: {}", check.length_message);
+    assert!(!check.is_valid(), "The reading should be invalid");
+}
+
+#[test]
+fn test_serialize_binary_rejects_oversized_fields() {
+    assert_eq!(
+        serialize_binary(&[Outcome::new(1 << 40, 1, 'A')]),
+        Err(SprintError::FieldOutOfRange { field: "s", index: Some(0), value: 1 << 40 }),
+        "An s past u32::MAX should be reported, not panic"
+    );
+    assert_eq!(
+        serialize_binary(&[Outcome::new(1, 2, 'A'), Outcome::new(1, 1 << 33, 'A')]),
+        Err(SprintError::FieldOutOfRange { field: "t", index: Some(1), value: 1 << 33 }),
+        "A t past u32::MAX should be reported with its outcome's position"
+    );
+}
+
+#[test]
+fn test_analyze_rle_long_runs() {
+    let n = 1usize << 40;
+    assert_eq!(analyze_rle(&[('A', n)]).unwrap(), all_outcomes_for_length(n, 'A'), "A 2^40-'A' run should skip whole sprints rather than walk them");
+    let runs = [('B', n - 1), ('A', n)];
+    assert_eq!(analyze_rle(&runs).unwrap(), vec![Outcome::new(1, n, 'A')], "Only the whole-log sprint survives B's opening run");
+    assert_eq!(analyze_rle(&[('A', usize::MAX), ('!', 5), ('B', 1)]), Err(SprintError::RunLengthOverflow { run: 2 }), "An overflowing total length should be reported");
+}